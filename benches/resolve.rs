@@ -0,0 +1,108 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use sqlparser::{dialect::GenericDialect, parser::Parser};
+use sqlwire::{resolve_all, ParameterSet, Value};
+
+fn and_chain(c: &mut Criterion) {
+    let n = 1000;
+    let conditions: Vec<String> = (1..=n).map(|i| format!("c{} = ${}", i, i)).collect();
+    let sql = format!("select * from t where {}", conditions.join(" and "));
+    let dialect = GenericDialect {};
+    let rs = Parser::parse_sql(&dialect, &sql).unwrap();
+    let ps = ParameterSet::from((1..=n).map(Value::from).collect());
+
+    c.bench_function("resolve 1000 and-ed placeholders", |b| {
+        b.iter(|| {
+            let mut rs = rs.clone();
+            resolve_all(&ps, black_box(&mut rs)).unwrap();
+            rs
+        })
+    });
+}
+
+fn scalar_projection(c: &mut Criterion) {
+    let n = 50;
+    let columns: Vec<String> = (1..=n).map(|i| format!("${}", i)).collect();
+    let sql = format!("select {} from t", columns.join(", "));
+    let dialect = GenericDialect {};
+    let rs = Parser::parse_sql(&dialect, &sql).unwrap();
+    let ps = ParameterSet::from(
+        (1..=n)
+            .map(|i| {
+                if i % 2 == 0 {
+                    Value::from(i)
+                } else {
+                    Value::from(format!("value {}", i))
+                }
+            })
+            .collect(),
+    );
+
+    c.bench_function("resolve 50 scalar placeholders", |b| {
+        b.iter(|| {
+            let mut rs = rs.clone();
+            resolve_all(&ps, black_box(&mut rs)).unwrap();
+            rs
+        })
+    });
+}
+
+/// Benchmarks resolving `sql` with values 1..=n, reporting placeholders/sec.
+fn bench_placeholders(c: &mut Criterion, name: &str, sql: &str, n: usize) {
+    let dialect = GenericDialect {};
+    let rs = Parser::parse_sql(&dialect, sql).unwrap();
+    let ps = ParameterSet::from((1..=n).map(Value::from).collect());
+
+    let mut group = c.benchmark_group("placeholders");
+    group.throughput(Throughput::Elements(n as u64));
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            let mut rs = rs.clone();
+            resolve_all(&ps, black_box(&mut rs)).unwrap();
+            rs
+        })
+    });
+    group.finish();
+}
+
+fn single(c: &mut Criterion) {
+    bench_placeholders(c, "single", "select * from t where id = $1", 1);
+}
+
+fn values_insert(c: &mut Criterion) {
+    let n = 1000;
+    let rows: Vec<String> = (0..n / 2)
+        .map(|i| format!("(${}, ${})", 2 * i + 1, 2 * i + 2))
+        .collect();
+    let sql = format!("insert into t (a, b) values {}", rows.join(", "));
+    bench_placeholders(c, "values insert", &sql, n);
+}
+
+fn nested_expression(c: &mut Criterion) {
+    // Stays under the parser's default recursion limit.
+    let n = 20;
+    let mut expr = "$1".to_string();
+    for i in 2..=n {
+        expr = format!("(({}) + ${})", expr, i);
+    }
+    let sql = format!("select {}", expr);
+    bench_placeholders(c, "nested expression", &sql, n);
+}
+
+fn batch(c: &mut Criterion) {
+    let n = 100;
+    let statements: Vec<String> = (1..=n)
+        .map(|i| format!("update t set a = ${} where id = {}", i, i))
+        .collect();
+    bench_placeholders(c, "batch", &statements.join(";\n"), n);
+}
+
+criterion_group!(
+    benches,
+    and_chain,
+    scalar_projection,
+    single,
+    values_insert,
+    nested_expression,
+    batch
+);
+criterion_main!(benches);
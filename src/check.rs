@@ -0,0 +1,158 @@
+use {
+    super::{resolve, resolve_all, result::Error, value::Value, Numbering, Parameters, R},
+    sqlparser::ast::{
+        visit_expressions, BinaryOperator, Expr, FunctionArg, FunctionArgExpr, FunctionArguments,
+        Statement, UnaryOperator, Value as AstValue,
+    },
+    std::ops::ControlFlow,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Numeric,
+    String,
+}
+
+const STRING_FUNCTIONS: &[&str] = &[
+    "upper",
+    "lower",
+    "length",
+    "char_length",
+    "concat",
+    "trim",
+    "ltrim",
+    "rtrim",
+    "substr",
+    "substring",
+    "replace",
+    "left",
+    "right",
+    "lpad",
+    "rpad",
+    "reverse",
+];
+
+fn placeholder(x: &Expr) -> Option<&str> {
+    match x {
+        Expr::Value(AstValue::Placeholder(p)) => Some(p),
+        _ => None,
+    }
+}
+
+fn literal_kind(x: &Expr) -> Option<Kind> {
+    match x {
+        Expr::Value(AstValue::Number(..)) => Some(Kind::Numeric),
+        Expr::Value(AstValue::SingleQuotedString(_)) => Some(Kind::String),
+        _ => None,
+    }
+}
+
+fn is_arithmetic(op: &BinaryOperator) -> bool {
+    matches!(
+        op,
+        BinaryOperator::Plus
+            | BinaryOperator::Minus
+            | BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Modulo
+    )
+}
+
+fn expect(sites: &mut Vec<(String, Kind)>, x: &Expr, kind: Kind) {
+    // A `?` the resolver doesn't reach is left unnumbered, and unresolved.
+    if let Some(p) = placeholder(x).filter(|p| *p != "?") {
+        sites.push((p.to_string(), kind));
+    }
+}
+
+/// Collects every placeholder whose parent expression implies a kind.
+fn expected_kinds(s: &Vec<Statement>) -> Vec<(String, Kind)> {
+    let mut sites = Vec::new();
+    let _ = visit_expressions(s, |x| {
+        match x {
+            Expr::BinaryOp { left, op, right } if is_arithmetic(op) => {
+                expect(&mut sites, left, Kind::Numeric);
+                expect(&mut sites, right, Kind::Numeric);
+            }
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::StringConcat,
+                right,
+            } => {
+                expect(&mut sites, left, Kind::String);
+                expect(&mut sites, right, Kind::String);
+            }
+            Expr::UnaryOp {
+                op: UnaryOperator::Plus | UnaryOperator::Minus,
+                expr,
+            } => {
+                expect(&mut sites, expr, Kind::Numeric);
+            }
+            Expr::Like { expr, pattern, .. }
+            | Expr::ILike { expr, pattern, .. }
+            | Expr::SimilarTo { expr, pattern, .. } => {
+                expect(&mut sites, expr, Kind::String);
+                expect(&mut sites, pattern, Kind::String);
+            }
+            Expr::Case {
+                operand: Some(operand),
+                conditions,
+                ..
+            } => {
+                // `CASE $1 WHEN 'a' ...`: the operand takes the kind of the
+                // WHEN literals, as long as they agree.
+                let mut kinds = conditions.iter().filter_map(literal_kind);
+                if let Some(kind) = kinds.next() {
+                    if kinds.all(|k| k == kind) {
+                        expect(&mut sites, operand, kind);
+                    }
+                }
+            }
+            Expr::Function(func) => {
+                let name = func.name.to_string().to_lowercase();
+                if let FunctionArguments::List(list) = &func.args {
+                    if STRING_FUNCTIONS.contains(&name.as_str()) {
+                        for arg in list.args.iter() {
+                            if let FunctionArg::Unnamed(FunctionArgExpr::Expr(x)) = arg {
+                                expect(&mut sites, x, Kind::String);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        ControlFlow::<()>::Continue(())
+    });
+    sites
+}
+
+/// NULL, typed or not, fits every kind.
+fn check_kind(p: &str, kind: Kind, v: &Value) -> R {
+    let ok = v.is_null()
+        || match kind {
+            Kind::Numeric => matches!(v, Value::Number(_)),
+            Kind::String => matches!(v, Value::String(_)),
+        };
+    if !ok {
+        return Err(Error::TypeMismatch(format!(
+            "{} expects {:?}, got {:?}",
+            p, kind, v
+        )));
+    }
+    Ok(())
+}
+
+/// Like `resolve_all`, but first checks that placeholders used in
+/// arithmetic, string or CASE-operand contexts are bound to values of a
+/// matching kind.
+pub fn resolve_all_checked(ps: &dyn Parameters, s: &mut [Statement]) -> R {
+    // Number `?` as resolve_all will, so each site checks the value it gets.
+    let mut numbered = s.to_vec();
+    Numbering::default().number(&mut numbered)?;
+    for (p, kind) in expected_kinds(&numbered) {
+        let v = resolve(ps, &p)?;
+        check_kind(&p, kind, &v)?;
+    }
+    resolve_all(ps, s)
+}
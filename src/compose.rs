@@ -0,0 +1,115 @@
+use {
+    super::{result::Error, ParameterSet},
+    sqlparser::{
+        ast::{
+            visit_expressions_mut, Cte, Expr, Ident, Query, Statement, TableAlias,
+            Value as AstValue, With,
+        },
+        dialect::Dialect,
+        parser::Parser,
+    },
+    std::ops::ControlFlow,
+};
+
+/// Stitches parameterized SQL fragments into a single query: each fragment
+/// added with `with` becomes a CTE feeding the final fragment, and every
+/// fragment's `$N` placeholders are shifted past the ones before it.
+pub struct FragmentComposer<'a> {
+    dialect: &'a dyn Dialect,
+    ctes: Vec<Cte>,
+    params: ParameterSet,
+}
+
+impl<'a> FragmentComposer<'a> {
+    pub fn new(dialect: &'a dyn Dialect) -> Self {
+        FragmentComposer {
+            dialect,
+            ctes: Vec::new(),
+            params: ParameterSet::default(),
+        }
+    }
+
+    /// Adds `sql` as a CTE named `name`.
+    pub fn with(mut self, name: &str, sql: &str, ps: ParameterSet) -> Result<Self, Error> {
+        let query = self.parse_fragment(sql, ps)?;
+        self.ctes.push(Cte {
+            alias: TableAlias {
+                name: Ident::new(name),
+                columns: vec![],
+            },
+            query,
+            from: None,
+            materialized: None,
+        });
+        Ok(self)
+    }
+
+    /// Adds the final fragment and returns the composed statement together
+    /// with the combined parameters.
+    pub fn finish(
+        mut self,
+        sql: &str,
+        ps: ParameterSet,
+    ) -> Result<(Statement, ParameterSet), Error> {
+        let mut query = self.parse_fragment(sql, ps)?;
+        if !self.ctes.is_empty() {
+            match query.with {
+                Some(ref mut with) => {
+                    self.ctes.append(&mut with.cte_tables);
+                    with.cte_tables = self.ctes;
+                }
+                None => {
+                    query.with = Some(With {
+                        recursive: false,
+                        cte_tables: self.ctes,
+                    });
+                }
+            }
+        }
+        Ok((Statement::Query(query), self.params))
+    }
+
+    fn parse_fragment(&mut self, sql: &str, mut ps: ParameterSet) -> Result<Box<Query>, Error> {
+        let mut rs = Parser::parse_sql(self.dialect, sql)?;
+        if rs.len() != 1 {
+            return Err(Error::Parse(format!(
+                "expected a single query fragment, got {} statements",
+                rs.len()
+            )));
+        }
+        let mut query = match rs.pop() {
+            Some(Statement::Query(query)) => query,
+            Some(s) => return Err(Error::Parse(format!("not a query: {}", s))),
+            None => unreachable!(),
+        };
+        renumber(&mut query, self.params.values.len(), ps.values.len())?;
+        self.params.values.append(&mut ps.values);
+        Ok(query)
+    }
+}
+
+/// Shifts every `$N` placeholder by `offset`. Placeholders of another form,
+/// or numbered past the fragment's own parameters, would collide with the
+/// neighbouring fragments and are rejected.
+fn renumber(q: &mut Query, offset: usize, count: usize) -> Result<(), Error> {
+    let result = visit_expressions_mut(q, |x| {
+        if let Expr::Value(AstValue::Placeholder(p)) = x {
+            match p.strip_prefix('$').and_then(|n| n.parse::<usize>().ok()) {
+                Some(i) if i > 0 && i <= count => {
+                    *p = format!("${}", i + offset);
+                }
+                _ => {
+                    return ControlFlow::Break(Error::Collision(format!(
+                        "placeholder {} cannot be renumbered",
+                        p
+                    )));
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    });
+    if let ControlFlow::Break(e) = result {
+        return Err(e);
+    }
+    Ok(())
+}
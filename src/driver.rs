@@ -0,0 +1,92 @@
+use {
+    super::{
+        internal, resolve, resolve_statement, result::Error, Numbering, Parameters, Rv, Value,
+    },
+    sqlparser::ast::{Expr, Statement, Value as AstValue},
+    std::cell::RefCell,
+};
+
+/// Placeholder syntax expected by a database driver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriverSyntax {
+    /// `$1`, as used by Postgres.
+    Dollar,
+    /// `?`, as used by MySQL and SQLite.
+    Question,
+    /// `:1`, as used by Oracle.
+    Colon,
+    /// `@p1`, as used by SQL Server.
+    AtP,
+}
+
+impl DriverSyntax {
+    fn placeholder(&self, n: usize) -> String {
+        match self {
+            DriverSyntax::Dollar => format!("${}", n),
+            DriverSyntax::Question => "?".to_string(),
+            DriverSyntax::Colon => format!(":{}", n),
+            DriverSyntax::AtP => format!("@p{}", n),
+        }
+    }
+}
+
+/// Rewrites placeholders into a driver's syntax as the resolver reaches
+/// them, recording the value each new position binds.
+struct Rewriting<'a> {
+    inner: &'a dyn Parameters,
+    syntax: DriverSyntax,
+    seen: RefCell<Vec<String>>,
+    values: RefCell<Vec<Value>>,
+}
+
+impl<'a> Parameters for Rewriting<'a> {
+    fn get(&self, i: usize) -> Rv {
+        self.inner.get(i)
+    }
+
+    fn get_named(&self, name: &str) -> Rv {
+        self.inner.get_named(name)
+    }
+
+    fn substitute(&self, p: &str, _: internal::Token) -> Result<Expr, Error> {
+        let reuse = self.syntax != DriverSyntax::Question;
+        let mut seen = self.seen.borrow_mut();
+        let n = match seen.iter().position(|q| reuse && q == p) {
+            Some(i) => i + 1,
+            None => {
+                let mut values = self.values.borrow_mut();
+                values.push(resolve(self.inner, p)?);
+                seen.push(p.to_string());
+                values.len()
+            }
+        };
+        Ok(Expr::Value(AstValue::Placeholder(
+            self.syntax.placeholder(n),
+        )))
+    }
+}
+
+/// Rewrites every placeholder into `syntax`, numbered from 1 in order of
+/// appearance, and returns the values to bind in that order. Numbered
+/// syntaxes reuse one position for a repeated placeholder; `?` takes one
+/// value per occurrence.
+pub fn to_driver_syntax(
+    ps: &dyn Parameters,
+    s: &mut [Statement],
+    syntax: DriverSyntax,
+) -> Result<Vec<Value>, Error> {
+    let numbering = Numbering::default();
+    numbering.number(s)?;
+    let rewriting = Rewriting {
+        inner: ps,
+        syntax,
+        seen: RefCell::default(),
+        values: RefCell::default(),
+    };
+    let result = s
+        .iter_mut()
+        .try_for_each(|stmt| resolve_statement(&rewriting, stmt));
+    numbering.restore(s);
+    result?;
+    Ok(rewriting.values.into_inner())
+}
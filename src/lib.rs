@@ -1,22 +1,76 @@
 // use bigdecimal::BigDecimal;
 use sqlparser::ast::{
-    visit_statements_mut, Expr, GroupByExpr, Query, SelectItem, SetExpr, Statement,
-    Value as AstValue,
+    visit_expressions, visit_expressions_mut, AlterColumnOperation, AlterTableOperation,
+    ColumnOption, CreateTableOptions, Expr, FunctionArg, FunctionArgExpr, FunctionArguments,
+    GroupByExpr, JoinConstraint, JoinOperator, MergeAction, MergeInsertExpr, MergeInsertKind,
+    ObjectName, OnConflictAction, OnInsert, OrderByExpr, PivotValueSource, Query, SelectItem,
+    SetExpr, ShowStatementFilter, SqlOption, Statement, Subscript, TableConstraint, TableFactor,
+    TableWithJoins, Value as AstValue, Visit, Visitor, WindowType,
+};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::{BTreeSet, HashMap},
+    ops::{ControlFlow, Range},
 };
-use std::ops::ControlFlow;
 
+mod check;
+mod compose;
+mod driver;
+mod limits;
+mod named;
+mod number;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod render;
+mod resolver;
 mod result;
+mod template;
 mod value;
 mod wire;
 
-pub use value::Value;
+pub use check::resolve_all_checked;
+pub use compose::FragmentComposer;
+pub use driver::{to_driver_syntax, DriverSyntax};
+pub use limits::{resolve_all_limited, RenderLimits};
+pub use named::NamedParameterSet;
+pub use number::Number;
+#[cfg(feature = "postgres")]
+pub use postgres::RowParameters;
+pub use render::{render_statement, resolve_all_with_options, RenderOptions};
+pub use resolver::Resolver;
+pub use template::{compile, parse_and_resolve_mixed, Template};
+pub use value::{Value, ValueKind};
 
-pub use result::{Error, R};
+pub use result::{Error, JsonError, R};
 
 pub type Rv = Result<Value, Error>;
 
 pub trait Parameters {
     fn get(&self, i: usize) -> Rv;
+
+    /// Looks up a `:name` or `@name` placeholder; `name` excludes the sigil.
+    fn get_named(&self, name: &str) -> Rv {
+        Err(Error::Notfound(format!(":{}", name)))
+    }
+
+    /// The expression placeholder `p` is replaced with. Only this crate
+    /// can override it, to number `?` placeholders.
+    #[doc(hidden)]
+    fn substitute(&self, p: &str, _: internal::Token) -> Result<Expr, Error> {
+        let v = lookup(self, p)?;
+        // Bound values may be secrets, so only the kind is logged unless
+        // the `trace-values` feature opts in.
+        #[cfg(all(feature = "tracing", not(feature = "trace-values")))]
+        tracing::debug!(placeholder = %p, kind = ?v.kind(), "substituted");
+        #[cfg(feature = "trace-values")]
+        tracing::debug!(placeholder = %p, kind = ?v.kind(), value = %v, "substituted");
+        Expr::try_from(v)
+    }
+}
+
+mod internal {
+    pub struct Token;
 }
 
 #[derive(Default)]
@@ -33,9 +87,70 @@ impl ParameterSet {
     pub fn from(values: Vec<Value>) -> Self {
         ParameterSet { values }
     }
+
+    /// Returns the distinct values, in order of first occurrence, and for
+    /// each original index the index of its value in the new set. Values
+    /// are only merged if they also render the same, so `1` and `1.0` stay
+    /// apart even though they compare equal.
+    pub fn dedup(&self) -> (ParameterSet, Vec<usize>) {
+        let mut seen: HashMap<(&Value, String), usize> = HashMap::new();
+        let mut deduped = ParameterSet::default();
+        let remap = self
+            .values
+            .iter()
+            .map(|v| {
+                *seen
+                    .entry((v, v.to_string()))
+                    .or_insert_with(|| deduped.add(v.clone()))
+            })
+            .collect();
+        (deduped, remap)
+    }
+
+    /// Checks that the `$N` placeholders used across `s` are exactly
+    /// `$1..=$n`, with `n` the number of bound values. `?` counts as the
+    /// position the resolver would bind it to.
+    pub fn validate_contiguous(&self, s: &[Statement]) -> R {
+        let mut numbered = s.to_vec();
+        Numbering::default().number(&mut numbered)?;
+        let mut used = BTreeSet::new();
+        for stmt in numbered.iter() {
+            for p in placeholder_names(stmt) {
+                if p.starts_with('$') || (p.starts_with('?') && p.len() > 1) {
+                    used.insert(placeholder_to_usize(&p));
+                }
+            }
+        }
+        let missing: Vec<String> = (1..=self.values.len())
+            .filter(|i| !used.contains(i))
+            .map(|i| format!("${}", i))
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::Positional(format!(
+                "{} not referenced",
+                missing.join(", ")
+            )));
+        }
+        let extra: Vec<String> = used
+            .iter()
+            .filter(|i| **i == 0 || **i > self.values.len())
+            .map(|i| format!("${}", i))
+            .collect();
+        if !extra.is_empty() {
+            return Err(Error::Positional(format!(
+                "{} beyond the {} bound values",
+                extra.join(", "),
+                self.values.len()
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl Parameters for ParameterSet {
+    /// Clones the value: the set is shared across placeholders and
+    /// statements, and counting uses to move the last one out costs more
+    /// than the clones it saves.
     fn get(&self, i: usize) -> Rv {
         // TODO: resolve the placeholder to an actual Value.
         if i > 0 && i <= self.values.len() {
@@ -48,20 +163,42 @@ impl Parameters for ParameterSet {
 }
 
 fn placeholder_to_usize(p: &str) -> usize {
-    let i_str = String::from_utf8(p.as_bytes()[1..].to_vec()).unwrap();
-    i_str.as_str().parse::<usize>().unwrap_or(0)
+    // Parse in place; this runs once per placeholder site.
+    p.get(1..)
+        .and_then(|i| i.parse::<usize>().ok())
+        .unwrap_or(0)
 }
 
-pub fn resolve(ps: &dyn Parameters, p: &str) -> Rv {
+/// The name of a `:name` or `@name` placeholder, without its sigil.
+pub(crate) fn placeholder_name(p: &str) -> Option<&str> {
+    let name = p.strip_prefix(':').or_else(|| p.strip_prefix('@'))?;
+    if name.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+fn lookup<P: Parameters + ?Sized>(ps: &P, p: &str) -> Rv {
+    if p == "?" {
+        // Only numbered while resolving a batch, as `?1`, `?2`, ...
+        return Err(Error::Notfound(p.to_string()));
+    }
+    if let Some(name) = placeholder_name(p) {
+        return ps.get_named(name);
+    }
     let i: usize = placeholder_to_usize(p);
     ps.get(i)
 }
 
+pub fn resolve(ps: &dyn Parameters, p: &str) -> Rv {
+    lookup(ps, p)
+}
+
 pub fn resolve_parameters_expr(ps: &dyn Parameters, x: &mut Expr) -> R {
     match x {
         Expr::Value(AstValue::Placeholder(p)) => {
-            let v = resolve(ps, p)?;
-            *x = v.into();
+            *x = ps.substitute(p, internal::Token)?;
         }
         Expr::IsNull(bv) => {
             let v = bv.as_mut();
@@ -71,6 +208,23 @@ pub fn resolve_parameters_expr(ps: &dyn Parameters, x: &mut Expr) -> R {
             let v = bv.as_mut();
             resolve_parameters_expr(ps, v)?;
         }
+        Expr::IsTrue(bv)
+        | Expr::IsNotTrue(bv)
+        | Expr::IsFalse(bv)
+        | Expr::IsNotFalse(bv)
+        | Expr::IsUnknown(bv)
+        | Expr::IsNotUnknown(bv) => {
+            let v = bv.as_mut();
+            resolve_parameters_expr(ps, v)?;
+        }
+        Expr::IsDistinctFrom(bl, br) | Expr::IsNotDistinctFrom(bl, br) => {
+            resolve_parameters_expr(ps, bl.as_mut())?;
+            resolve_parameters_expr(ps, br.as_mut())?;
+        }
+        Expr::AnyOp { left, right, .. } | Expr::AllOp { left, right, .. } => {
+            resolve_parameters_expr(ps, left.as_mut())?;
+            resolve_parameters_expr(ps, right.as_mut())?;
+        }
         Expr::InList {
             expr,
             list,
@@ -127,6 +281,72 @@ pub fn resolve_parameters_expr(ps: &dyn Parameters, x: &mut Expr) -> R {
             let p = pattern.as_mut();
             resolve_parameters_expr(ps, p)?;
         }
+        Expr::SimilarTo {
+            expr,
+            negated: _,
+            pattern,
+            escape_char: _,
+        } => {
+            resolve_parameters_expr(ps, expr.as_mut())?;
+            resolve_parameters_expr(ps, pattern.as_mut())?;
+        }
+        Expr::Substring {
+            expr,
+            substring_from,
+            substring_for,
+            special: _,
+        } => {
+            resolve_parameters_expr(ps, expr.as_mut())?;
+            if let Some(x) = substring_from {
+                resolve_parameters_expr(ps, x.as_mut())?;
+            }
+            if let Some(x) = substring_for {
+                resolve_parameters_expr(ps, x.as_mut())?;
+            }
+        }
+        Expr::Extract {
+            field: _,
+            syntax: _,
+            expr,
+        } => {
+            resolve_parameters_expr(ps, expr.as_mut())?;
+        }
+        Expr::AtTimeZone {
+            timestamp,
+            time_zone,
+        } => {
+            resolve_parameters_expr(ps, timestamp.as_mut())?;
+            resolve_parameters_expr(ps, time_zone.as_mut())?;
+        }
+        Expr::Trim {
+            expr,
+            trim_where: _,
+            trim_what,
+            trim_characters,
+        } => {
+            if let Some(x) = trim_what {
+                resolve_parameters_expr(ps, x.as_mut())?;
+            }
+            resolve_parameters_expr(ps, expr.as_mut())?;
+            if let Some(exprs) = trim_characters {
+                for x in exprs.iter_mut() {
+                    resolve_parameters_expr(ps, x)?;
+                }
+            }
+        }
+        Expr::Overlay {
+            expr,
+            overlay_what,
+            overlay_from,
+            overlay_for,
+        } => {
+            resolve_parameters_expr(ps, expr.as_mut())?;
+            resolve_parameters_expr(ps, overlay_what.as_mut())?;
+            resolve_parameters_expr(ps, overlay_from.as_mut())?;
+            if let Some(x) = overlay_for {
+                resolve_parameters_expr(ps, x.as_mut())?;
+            }
+        }
         Expr::BinaryOp { left, op: _, right } => {
             let vl = left.as_mut();
             resolve_parameters_expr(ps, vl)?;
@@ -141,6 +361,11 @@ pub fn resolve_parameters_expr(ps: &dyn Parameters, x: &mut Expr) -> R {
             let v = bv.as_mut();
             resolve_parameters_expr(ps, v)?;
         }
+        Expr::Tuple(exprs) => {
+            for x in exprs.iter_mut() {
+                resolve_parameters_expr(ps, x)?;
+            }
+        }
         Expr::Exists {
             subquery,
             negated: _,
@@ -162,11 +387,9 @@ pub fn resolve_parameters_expr(ps: &dyn Parameters, x: &mut Expr) -> R {
                 let v = bv.as_mut();
                 resolve_parameters_expr(ps, v)?;
             }
-            for expr in conditions.iter_mut() {
-                resolve_parameters_expr(ps, expr)?;
-            }
-            for expr in results.iter_mut() {
-                resolve_parameters_expr(ps, expr)?;
+            for (cond, result) in conditions.iter_mut().zip(results.iter_mut()) {
+                resolve_parameters_expr(ps, cond)?;
+                resolve_parameters_expr(ps, result)?;
             }
             if let Some(bv) = else_result {
                 let v = bv.as_mut();
@@ -182,32 +405,246 @@ pub fn resolve_parameters_expr(ps: &dyn Parameters, x: &mut Expr) -> R {
                 resolve_parameters_expr(ps, expr)?;
             }
         }
+        Expr::Cast {
+            kind: _,
+            expr,
+            data_type,
+            format,
+        } => {
+            let v = expr.as_mut();
+            resolve_parameters_expr(ps, v)?;
+            // CAST(DATE '...' AS DATE) is redundant; keep the typed string.
+            let same_type = matches!(v, Expr::TypedString { data_type: t, .. } if t == data_type);
+            if same_type && format.is_none() {
+                *x = std::mem::replace(v, Expr::Value(AstValue::Null));
+            }
+        }
+        Expr::Function(func) => {
+            if let FunctionArguments::List(list) = &mut func.args {
+                for arg in list.args.iter_mut() {
+                    resolve_parameters_function_arg(ps, arg)?;
+                }
+            }
+            if let Some(filter) = &mut func.filter {
+                resolve_parameters_expr(ps, filter.as_mut())?;
+            }
+            if let Some(WindowType::WindowSpec(spec)) = &mut func.over {
+                for expr in spec.partition_by.iter_mut() {
+                    resolve_parameters_expr(ps, expr)?;
+                }
+                resolve_parameters_order_by(ps, &mut spec.order_by)?;
+            }
+        }
+        Expr::Map(map) => {
+            for entry in map.entries.iter_mut() {
+                resolve_parameters_expr(ps, entry.key.as_mut())?;
+                resolve_parameters_expr(ps, entry.value.as_mut())?;
+            }
+        }
+        Expr::MapAccess { column, keys } => {
+            resolve_parameters_expr(ps, column.as_mut())?;
+            for key in keys.iter_mut() {
+                resolve_parameters_expr(ps, &mut key.key)?;
+            }
+        }
+        Expr::Subscript { expr, subscript } => {
+            resolve_parameters_expr(ps, expr.as_mut())?;
+            match subscript.as_mut() {
+                Subscript::Index { index } => {
+                    resolve_parameters_expr(ps, index)?;
+                }
+                Subscript::Slice {
+                    lower_bound,
+                    upper_bound,
+                    stride,
+                } => {
+                    for x in [lower_bound, upper_bound, stride].into_iter().flatten() {
+                        resolve_parameters_expr(ps, x)?;
+                    }
+                }
+            }
+        }
+        Expr::Dictionary(fields) => {
+            for field in fields.iter_mut() {
+                resolve_parameters_expr(ps, field.value.as_mut())?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn resolve_parameters_function_arg(ps: &dyn Parameters, arg: &mut FunctionArg) -> R {
+    match arg {
+        FunctionArg::Named {
+            name: _,
+            arg: FunctionArgExpr::Expr(x),
+            operator: _,
+        } => {
+            resolve_parameters_expr(ps, x)?;
+        }
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(x)) => {
+            resolve_parameters_expr(ps, x)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn resolve_parameters_order_by(ps: &dyn Parameters, exprs: &mut [OrderByExpr]) -> R {
+    for x in exprs.iter_mut() {
+        resolve_parameters_expr(ps, &mut x.expr)?;
+    }
+    Ok(())
+}
+
+fn resolve_parameters_table_factor(ps: &dyn Parameters, t: &mut TableFactor) -> R {
+    match t {
+        TableFactor::Table {
+            args: Some(args), ..
+        } => {
+            for arg in args.args.iter_mut() {
+                resolve_parameters_function_arg(ps, arg)?;
+            }
+        }
+        TableFactor::Function { args, .. } => {
+            for arg in args.iter_mut() {
+                resolve_parameters_function_arg(ps, arg)?;
+            }
+        }
+        TableFactor::TableFunction { expr, alias: _ } => {
+            resolve_parameters_expr(ps, expr)?;
+        }
+        TableFactor::Derived { subquery, .. } => {
+            resolve_parameters_query(ps, subquery.as_mut())?;
+        }
+        TableFactor::NestedJoin {
+            table_with_joins,
+            alias: _,
+        } => {
+            resolve_parameters_table_with_joins(ps, table_with_joins.as_mut())?;
+        }
+        TableFactor::Pivot {
+            table,
+            aggregate_functions,
+            value_source,
+            default_on_null,
+            ..
+        } => {
+            resolve_parameters_table_factor(ps, table.as_mut())?;
+            for func in aggregate_functions.iter_mut() {
+                resolve_parameters_expr(ps, &mut func.expr)?;
+            }
+            match value_source {
+                PivotValueSource::List(values) => {
+                    for v in values.iter_mut() {
+                        resolve_parameters_expr(ps, &mut v.expr)?;
+                    }
+                }
+                PivotValueSource::Any(order_by) => {
+                    resolve_parameters_order_by(ps, order_by)?;
+                }
+                PivotValueSource::Subquery(q) => {
+                    resolve_parameters_query(ps, q)?;
+                }
+            }
+            if let Some(x) = default_on_null {
+                resolve_parameters_expr(ps, x)?;
+            }
+        }
+        TableFactor::Unpivot { table, .. } => {
+            resolve_parameters_table_factor(ps, table.as_mut())?;
+        }
         _ => {}
     }
     Ok(())
 }
 
+fn resolve_parameters_table_with_joins(ps: &dyn Parameters, t: &mut TableWithJoins) -> R {
+    resolve_parameters_table_factor(ps, &mut t.relation)?;
+    for join in t.joins.iter_mut() {
+        resolve_parameters_table_factor(ps, &mut join.relation)?;
+        let constraint = match &mut join.join_operator {
+            JoinOperator::Inner(c)
+            | JoinOperator::LeftOuter(c)
+            | JoinOperator::RightOuter(c)
+            | JoinOperator::FullOuter(c)
+            | JoinOperator::LeftSemi(c)
+            | JoinOperator::RightSemi(c)
+            | JoinOperator::LeftAnti(c)
+            | JoinOperator::RightAnti(c) => Some(c),
+            JoinOperator::AsOf {
+                match_condition,
+                constraint,
+            } => {
+                resolve_parameters_expr(ps, match_condition)?;
+                Some(constraint)
+            }
+            _ => None,
+        };
+        if let Some(JoinConstraint::On(x)) = constraint {
+            resolve_parameters_expr(ps, x)?;
+        }
+    }
+    Ok(())
+}
+
 fn resolve_parameters_query(ps: &dyn Parameters, q: &mut Query) -> R {
-    let body = q.body.as_mut();
+    if let Some(ref mut with) = q.with {
+        for cte in with.cte_tables.iter_mut() {
+            resolve_parameters_query(ps, cte.query.as_mut())?;
+        }
+    }
+    resolve_parameters_set_expr(ps, q.body.as_mut())?;
+    if let Some(order_by) = &mut q.order_by {
+        resolve_parameters_order_by(ps, &mut order_by.exprs)?;
+    }
+    if let Some(limit) = &mut q.limit {
+        resolve_parameters_expr(ps, limit)?;
+    }
+    if let Some(offset) = &mut q.offset {
+        resolve_parameters_expr(ps, &mut offset.value)?;
+    }
+    if let Some(fetch) = &mut q.fetch {
+        if let Some(quantity) = &mut fetch.quantity {
+            resolve_parameters_expr(ps, quantity)?;
+        }
+    }
+    Ok(())
+}
+
+fn resolve_parameters_select_item(ps: &dyn Parameters, select_item: &mut SelectItem) -> R {
+    match select_item {
+        SelectItem::UnnamedExpr(expr) => {
+            resolve_parameters_expr(ps, expr)?;
+        }
+        SelectItem::ExprWithAlias { expr, alias: _ } => {
+            resolve_parameters_expr(ps, expr)?;
+        }
+        SelectItem::Wildcard(options) | SelectItem::QualifiedWildcard(_, options) => {
+            if let Some(replace) = &mut options.opt_replace {
+                for item in replace.items.iter_mut() {
+                    resolve_parameters_expr(ps, &mut item.expr)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn resolve_parameters_set_expr(ps: &dyn Parameters, body: &mut SetExpr) -> R {
     match body {
         SetExpr::Select(bs) => {
             let s = bs.as_mut();
+            for select_item in s.projection.iter_mut() {
+                resolve_parameters_select_item(ps, select_item)?;
+            }
+            for table in s.from.iter_mut() {
+                resolve_parameters_table_with_joins(ps, table)?;
+            }
             if let Some(ref mut selection) = s.selection {
                 resolve_parameters_expr(ps, selection)?;
             }
-            for select_item in s.projection.iter_mut() {
-                match select_item {
-                    SelectItem::UnnamedExpr(expr) => {
-                        resolve_parameters_expr(ps, expr)?;
-                    }
-                    SelectItem::ExprWithAlias { expr, alias: _ } => {
-                        resolve_parameters_expr(ps, expr)?;
-                    }
-                    _ => {
-                        // todo!();
-                    }
-                }
-            }
             if let GroupByExpr::Expressions(exprs, _) = &mut s.group_by {
                 for expr in exprs.iter_mut() {
                     resolve_parameters_expr(ps, expr)?;
@@ -224,9 +661,83 @@ fn resolve_parameters_query(ps: &dyn Parameters, q: &mut Query) -> R {
                 }
             }
         }
-        _ => {
-            todo!();
+        SetExpr::Table(_) => {
+            // `TABLE name` has nothing to resolve.
+        }
+        SetExpr::SetOperation { left, right, .. } => {
+            resolve_parameters_set_expr(ps, left.as_mut())?;
+            resolve_parameters_set_expr(ps, right.as_mut())?;
+        }
+        SetExpr::Query(q) => {
+            resolve_parameters_query(ps, q.as_mut())?;
+        }
+        SetExpr::Insert(stmt) | SetExpr::Update(stmt) => {
+            resolve_statement(ps, stmt)?;
+        }
+    }
+    Ok(())
+}
+
+fn resolve_on_insert(ps: &dyn Parameters, on: &mut OnInsert) -> R {
+    match on {
+        OnInsert::DuplicateKeyUpdate(assignments) => {
+            for x in assignments.iter_mut() {
+                resolve_parameters_expr(ps, &mut x.value)?;
+            }
+        }
+        OnInsert::OnConflict(on_conflict) => {
+            // the conflict target is columns or a constraint name only;
+            // sqlparser keeps no partial-index predicate there.
+            if let OnConflictAction::DoUpdate(update) = &mut on_conflict.action {
+                for x in update.assignments.iter_mut() {
+                    resolve_parameters_expr(ps, &mut x.value)?;
+                }
+                if let Some(ref mut selection) = update.selection {
+                    resolve_parameters_expr(ps, selection)?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn resolve_sql_options(ps: &dyn Parameters, options: &mut [SqlOption]) -> R {
+    for option in options.iter_mut() {
+        match option {
+            SqlOption::KeyValue { key: _, value } => {
+                resolve_parameters_expr(ps, value)?;
+            }
+            SqlOption::Partition { for_values, .. } => {
+                for x in for_values.iter_mut() {
+                    resolve_parameters_expr(ps, x)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn resolve_column_option(ps: &dyn Parameters, option: &mut ColumnOption) -> R {
+    match option {
+        ColumnOption::Default(x) | ColumnOption::Check(x) => {
+            resolve_parameters_expr(ps, x)?;
+        }
+        ColumnOption::Generated {
+            generation_expr: Some(x),
+            ..
+        } => {
+            resolve_parameters_expr(ps, x)?;
         }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn resolve_show_filter(ps: &dyn Parameters, filter: &mut Option<ShowStatementFilter>) -> R {
+    if let Some(ShowStatementFilter::Where(expr)) = filter {
+        resolve_parameters_expr(ps, expr)?;
     }
     Ok(())
 }
@@ -236,66 +747,558 @@ pub fn resolve_statement(ps: &dyn Parameters, s: &mut Statement) -> R {
         Statement::Query(query) => {
             resolve_parameters_query(ps, query)?;
         }
+        Statement::Explain { statement, .. } => {
+            resolve_statement(ps, statement)?;
+        }
         Statement::Insert(insert) => {
             if let Some(ref mut source) = insert.source {
                 resolve_parameters_query(ps, source)?;
             }
+            if let Some(ref mut on) = insert.on {
+                resolve_on_insert(ps, on)?;
+            }
         }
         Statement::Update {
-            table: _,
+            table,
             assignments,
-            from: _,
-            selection: Some(expr),
+            from,
+            selection,
             returning: _,
         } => {
+            resolve_parameters_table_with_joins(ps, table)?;
             for x in assignments.iter_mut() {
                 resolve_parameters_expr(ps, &mut x.value)?;
             }
-            resolve_parameters_expr(ps, expr)?;
+            if let Some(from) = from {
+                resolve_parameters_table_with_joins(ps, from)?;
+            }
+            if let Some(expr) = selection {
+                resolve_parameters_expr(ps, expr)?;
+            }
         }
         Statement::Delete(delete) => {
             if let Some(ref mut expr) = delete.selection {
                 resolve_parameters_expr(ps, expr)?;
             }
+            if let Some(returning) = &mut delete.returning {
+                for select_item in returning.iter_mut() {
+                    resolve_parameters_select_item(ps, select_item)?;
+                }
+            }
+            resolve_parameters_order_by(ps, &mut delete.order_by)?;
+            if let Some(limit) = &mut delete.limit {
+                resolve_parameters_expr(ps, limit)?;
+            }
+        }
+        Statement::Merge {
+            table,
+            source,
+            on,
+            clauses,
+            ..
+        } => {
+            resolve_parameters_table_factor(ps, table)?;
+            resolve_parameters_table_factor(ps, source)?;
+            resolve_parameters_expr(ps, on.as_mut())?;
+            for clause in clauses.iter_mut() {
+                if let Some(predicate) = &mut clause.predicate {
+                    resolve_parameters_expr(ps, predicate)?;
+                }
+                match &mut clause.action {
+                    MergeAction::Insert(MergeInsertExpr {
+                        kind: MergeInsertKind::Values(values),
+                        ..
+                    }) => {
+                        for row in values.rows.iter_mut() {
+                            for expr in row.iter_mut() {
+                                resolve_parameters_expr(ps, expr)?;
+                            }
+                        }
+                    }
+                    MergeAction::Update { assignments } => {
+                        for x in assignments.iter_mut() {
+                            resolve_parameters_expr(ps, &mut x.value)?;
+                        }
+                    }
+                    _ => {}
+                }
+            }
         }
         Statement::CreateTable(create_table) => {
+            for column in create_table.columns.iter_mut() {
+                for def in column.options.iter_mut() {
+                    resolve_column_option(ps, &mut def.option)?;
+                }
+            }
+            for constraint in create_table.constraints.iter_mut() {
+                if let TableConstraint::Check { name: _, expr } = constraint {
+                    resolve_parameters_expr(ps, expr.as_mut())?;
+                }
+            }
             if let Some(ref mut query_boxed) = create_table.query {
                 let query = query_boxed.as_mut();
                 resolve_parameters_query(ps, query)?;
             }
         }
+        Statement::CreateView { query, options, .. } => {
+            if let CreateTableOptions::With(options) | CreateTableOptions::Options(options) =
+                options
+            {
+                resolve_sql_options(ps, options)?;
+            }
+            resolve_parameters_query(ps, query.as_mut())?;
+        }
+        Statement::AlterTable { operations, .. } => {
+            for op in operations.iter_mut() {
+                match op {
+                    AlterTableOperation::AddConstraint(TableConstraint::Check {
+                        name: _,
+                        expr,
+                    }) => {
+                        resolve_parameters_expr(ps, expr.as_mut())?;
+                    }
+                    AlterTableOperation::AddColumn { column_def, .. } => {
+                        for def in column_def.options.iter_mut() {
+                            resolve_column_option(ps, &mut def.option)?;
+                        }
+                    }
+                    AlterTableOperation::AlterColumn {
+                        column_name: _,
+                        op: AlterColumnOperation::SetDefault { value },
+                    } => {
+                        resolve_parameters_expr(ps, value)?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Statement::Analyze {
+            partitions: Some(exprs),
+            ..
+        } => {
+            for expr in exprs.iter_mut() {
+                resolve_parameters_expr(ps, expr)?;
+            }
+        }
+        Statement::ShowFunctions { filter }
+        | Statement::ShowStatus { filter, .. }
+        | Statement::ShowVariables { filter, .. }
+        | Statement::ShowColumns { filter, .. }
+        | Statement::ShowTables { filter, .. }
+        | Statement::ShowCollation { filter } => {
+            resolve_show_filter(ps, filter)?;
+        }
+        Statement::ShowVariable { variable: _ } => {
+            // names only, nothing to resolve.
+        }
         _ => {}
     }
     Ok(())
 }
 
-pub fn resolve_all(ps: &dyn Parameters, s: &mut Vec<Statement>) -> R {
-    let result: ControlFlow<Error, ()> =
-        visit_statements_mut(s, |stmt| match resolve_statement(ps, stmt) {
-            Ok(_) => ControlFlow::Continue(()),
-            Err(e) => ControlFlow::Break(e),
-        });
-    if let ControlFlow::Break(e) = result {
-        return Err(e);
-    }
-    Ok(())
+/// Numbers `?` placeholders `?1`, `?2`, ... in the order the resolver
+/// reaches them, so that they resolve like `$N`, and rejects mixing them
+/// with explicit `$N` placeholders.
+#[derive(Default)]
+pub(crate) struct Numbering {
+    next: Cell<usize>,
+    numbered: Cell<bool>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{resolve_all, ParameterSet};
-    use sqlparser::{dialect::GenericDialect, parser::Parser};
+impl Numbering {
+    pub(crate) fn number(&self, s: &mut [Statement]) -> R {
+        s.iter_mut()
+            .try_for_each(|stmt| resolve_statement(self, stmt))
+    }
 
-    #[test]
-    fn sql_parsing_resolving() {
-        let sql = "create table test(x int, y int, title varchar);
-insert into test (x, y, title) values($1, $2, $3);
-select $1 px, t.* from test t;";
-        let dialect = GenericDialect {};
-        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+    /// Turns the `?N` left by `number` back into `?`.
+    pub(crate) fn restore(&self, s: &mut [Statement]) {
+        if self.next.get() == 0 {
+            return;
+        }
+        for stmt in s.iter_mut() {
+            let _ = visit_expressions_mut(stmt, |x| {
+                if let Expr::Value(AstValue::Placeholder(p)) = x {
+                    if p.starts_with('?') {
+                        *p = "?".to_string();
+                    }
+                }
+                ControlFlow::<()>::Continue(())
+            });
+        }
+    }
+}
 
-        let mut ps = ParameterSet::default();
-        ps.add(123.into());
+impl Parameters for Numbering {
+    fn get(&self, i: usize) -> Rv {
+        Err(Error::Notfound(format!("${}", i)))
+    }
+
+    fn substitute(&self, p: &str, _: internal::Token) -> Result<Expr, Error> {
+        if p == "?" {
+            if self.numbered.get() {
+                return Err(Error::Positional("? mixed with $N".to_string()));
+            }
+            let i = self.next.get() + 1;
+            self.next.set(i);
+            return Ok(Expr::Value(AstValue::Placeholder(format!("?{}", i))));
+        }
+        if placeholder_name(p).is_none() {
+            if self.next.get() > 0 {
+                return Err(Error::Positional(format!("{} mixed with ?", p)));
+            }
+            self.numbered.set(true);
+        }
+        Ok(Expr::Value(AstValue::Placeholder(p.to_string())))
+    }
+}
+
+/// Resolves each statement in turn. Nested statements, such as the
+/// `INSERT` under a `WITH`, are reached through their parent, so `?` is
+/// numbered in source order.
+fn resolve_statements(ps: &dyn Parameters, s: &mut [Statement]) -> R {
+    let numbering = Numbering::default();
+    let result = numbering.number(s).and_then(|_| {
+        s.iter_mut()
+            .try_for_each(|stmt| resolve_statement(ps, stmt))
+    });
+    numbering.restore(s);
+    result
+}
+
+pub fn resolve_all(ps: &dyn Parameters, s: &mut [Statement]) -> R {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!(
+        "resolve_all",
+        statements = s.len(),
+        distinct_placeholders = s.iter().map(|x| placeholder_names(x).len()).sum::<usize>(),
+    )
+    .entered();
+    resolve_statements(ps, s)
+}
+
+/// Like `resolve_all`, but attempts every statement and returns all
+/// failures with their indices. Failed statements are left untouched.
+///
+/// `?` placeholders are numbered across the whole batch, and a failed
+/// statement still takes one value per `?` in it, so later statements
+/// bind the same values as they would under `resolve_all`.
+pub fn resolve_all_collect(
+    ps: &dyn Parameters,
+    s: &mut [Statement],
+) -> Result<(), Vec<(usize, Error)>> {
+    let numbering = Numbering::default();
+    let mut errors = vec![];
+    for (i, stmt) in s.iter_mut().enumerate() {
+        let mut resolved = stmt.clone();
+        let result = numbering
+            .number(std::slice::from_mut(&mut resolved))
+            .and_then(|_| resolve_statement(ps, &mut resolved));
+        match result {
+            Ok(_) => {
+                numbering.restore(std::slice::from_mut(&mut resolved));
+                *stmt = resolved;
+            }
+            Err(e) => errors.push((i, e)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Like `resolve_all`, but fails if any placeholder is left in place
+/// because its statement or expression kind is not handled.
+pub fn resolve_all_strict(ps: &dyn Parameters, s: &mut [Statement]) -> R {
+    resolve_all(ps, s)?;
+    check_resolved(s)
+}
+
+/// Fails if any placeholder is left in the statements.
+pub(crate) fn check_resolved(s: &[Statement]) -> R {
+    for stmt in s.iter() {
+        let names = placeholder_names(stmt);
+        if !names.is_empty() {
+            let names: Vec<String> = names.into_iter().collect();
+            return Err(Error::Unresolved(format!(
+                "{} in: {}",
+                names.join(", "),
+                stmt
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Inlines scalar values, and renumbers the placeholders of the
+/// collections it leaves in place.
+struct Scalars<'a> {
+    inner: &'a dyn Parameters,
+    anonymous: bool,
+    deferred: RefCell<Vec<usize>>,
+}
+
+impl<'a> Parameters for Scalars<'a> {
+    fn get(&self, i: usize) -> Rv {
+        self.inner.get(i)
+    }
+
+    fn get_named(&self, name: &str) -> Rv {
+        self.inner.get_named(name)
+    }
+
+    fn substitute(&self, p: &str, _: internal::Token) -> Result<Expr, Error> {
+        let v = lookup(self.inner, p)?;
+        if !matches!(v, Value::Array(_) | Value::Dict(_)) {
+            return Expr::try_from(v);
+        }
+        if placeholder_name(p).is_some() {
+            return Ok(Expr::Value(AstValue::Placeholder(p.to_string())));
+        }
+        let i = placeholder_to_usize(p);
+        let mut deferred = self.deferred.borrow_mut();
+        let n = match deferred.iter().position(|d| *d == i) {
+            Some(n) if !self.anonymous => n + 1,
+            _ => {
+                deferred.push(i);
+                deferred.len()
+            }
+        };
+        let p = if self.anonymous {
+            "?".to_string()
+        } else {
+            format!("{}{}", &p[..1], n)
+        };
+        Ok(Expr::Value(AstValue::Placeholder(p)))
+    }
+}
+
+/// Like `resolve_all`, but only inlines scalar values. Placeholders bound
+/// to an array or dict are left in place for the driver to bind: numbered
+/// ones renumbered from 1 in order of first appearance, and `?` as it is.
+/// Returns the 1-based index into `ps` of the value each remaining
+/// position binds. Named placeholders keep their name and aren't listed.
+pub fn resolve_all_scalars(ps: &dyn Parameters, s: &mut [Statement]) -> Result<Vec<usize>, Error> {
+    let numbering = Numbering::default();
+    numbering.number(s)?;
+    let scalars = Scalars {
+        inner: ps,
+        anonymous: numbering.next.get() > 0,
+        deferred: RefCell::default(),
+    };
+    let result = s
+        .iter_mut()
+        .try_for_each(|stmt| resolve_statement(&scalars, stmt));
+    numbering.restore(s);
+    result?;
+    Ok(scalars.deferred.into_inner())
+}
+
+/// Returns true if any expression in the statement is a placeholder.
+pub fn has_placeholders(s: &Statement) -> bool {
+    let result = visit_expressions(s, |x| match x {
+        Expr::Value(AstValue::Placeholder(_)) => ControlFlow::Break(()),
+        _ => ControlFlow::Continue(()),
+    });
+    result.is_break()
+}
+
+/// Replaces every `target` placeholder with a clone of `replacement`,
+/// e.g. a column reference rather than a bound value.
+pub fn substitute_placeholder(s: &mut Statement, target: &str, replacement: &Expr) {
+    let _ = visit_expressions_mut(s, |x| {
+        if matches!(x, Expr::Value(AstValue::Placeholder(p)) if p == target) {
+            *x = replacement.clone();
+        }
+        ControlFlow::<()>::Continue(())
+    });
+}
+
+/// Calls `f` with every expression node in the statement, outer nodes
+/// before the expressions nested in them.
+pub fn for_each_expr(s: &Statement, mut f: impl FnMut(&Expr)) {
+    let _ = visit_expressions(s, |x| {
+        f(x);
+        ControlFlow::<()>::Continue(())
+    });
+}
+
+/// Returns the distinct placeholder texts (`$1`, `:name`, `?`, ...) used
+/// anywhere in the statement.
+pub fn placeholder_names(s: &Statement) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let _ = visit_expressions(s, |x| {
+        if let Expr::Value(AstValue::Placeholder(p)) = x {
+            names.insert(p.clone());
+        }
+        ControlFlow::<()>::Continue(())
+    });
+    names
+}
+
+/// Counts the `?` the resolver reaches, leaving the statement as it is.
+#[derive(Default)]
+struct QuestionCount(Cell<usize>);
+
+impl Parameters for QuestionCount {
+    fn get(&self, i: usize) -> Rv {
+        Err(Error::Notfound(format!("${}", i)))
+    }
+
+    fn substitute(&self, p: &str, _: internal::Token) -> Result<Expr, Error> {
+        if p == "?" {
+            self.0.set(self.0.get() + 1);
+        }
+        Ok(Expr::Value(AstValue::Placeholder(p.to_string())))
+    }
+}
+
+/// For each statement, the range of the batch's sequential `?` parameters
+/// it consumes, in statement order. Only the `?` that `resolve_all` binds
+/// are counted.
+pub fn split_parameters_by_statement(stmts: &[Statement]) -> Vec<Range<usize>> {
+    let counter = QuestionCount::default();
+    let mut ranges = Vec::with_capacity(stmts.len());
+    for stmt in stmts.iter() {
+        let start = counter.0.get();
+        let _ = resolve_statement(&counter, &mut stmt.clone());
+        ranges.push(start..counter.0.get());
+    }
+    ranges
+}
+
+/// The CTEs a query defines, and how many of them are visible at the point
+/// the walk has reached: a CTE's body sees only the ones before it unless
+/// the `WITH` is recursive, and the query body sees them all.
+struct CteScope {
+    names: Vec<String>,
+    bodies: Vec<*const Query>,
+    recursive: bool,
+    visible: usize,
+}
+
+#[derive(Default)]
+struct RelationCollector {
+    tables: BTreeSet<String>,
+    scopes: Vec<CteScope>,
+}
+
+impl RelationCollector {
+    fn is_cte(&self, relation: &ObjectName) -> bool {
+        let [name] = relation.0.as_slice() else {
+            return false;
+        };
+        self.scopes
+            .iter()
+            .any(|scope| scope.names[..scope.visible].contains(&name.value))
+    }
+
+    /// Moves the enclosing scope to the CTE whose body is `query`, if it is
+    /// one. `done` is true once the body has been walked.
+    fn enter_cte_body(&mut self, query: &Query, done: bool) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        if let Some(i) = scope.bodies.iter().position(|b| std::ptr::eq(*b, query)) {
+            scope.visible = if scope.recursive {
+                scope.names.len()
+            } else {
+                i + usize::from(done)
+            };
+        }
+    }
+}
+
+impl Visitor for RelationCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<()> {
+        self.enter_cte_body(query, false);
+        let ctes = query
+            .with
+            .as_ref()
+            .map(|w| &w.cte_tables[..])
+            .unwrap_or(&[]);
+        let recursive = query.with.as_ref().is_some_and(|w| w.recursive);
+        self.scopes.push(CteScope {
+            names: ctes.iter().map(|c| c.alias.name.value.clone()).collect(),
+            bodies: ctes.iter().map(|c| &*c.query as *const Query).collect(),
+            recursive,
+            visible: if recursive { ctes.len() } else { 0 },
+        });
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_query(&mut self, query: &Query) -> ControlFlow<()> {
+        self.scopes.pop();
+        self.enter_cte_body(query, true);
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<()> {
+        if !self.is_cte(relation) {
+            self.tables.insert(relation.to_string());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Returns the names of the tables the statement reads or writes, across
+/// FROM clauses, joins, subqueries and CTE bodies. A name refers to a CTE
+/// rather than a table only where that CTE is in scope: in the rest of its
+/// query, in later CTEs, and in its own body when the `WITH` is recursive.
+pub fn referenced_tables(s: &Statement) -> BTreeSet<String> {
+    let mut collector = RelationCollector::default();
+    let _ = s.visit(&mut collector);
+    collector.tables
+}
+
+/// Resolves a copy of the statement, leaving the original untouched.
+/// Statements without placeholders are borrowed instead of cloned.
+pub fn resolve_cloned<'a>(
+    ps: &dyn Parameters,
+    s: &'a Statement,
+) -> Result<Cow<'a, Statement>, Error> {
+    if !has_placeholders(s) {
+        return Ok(Cow::Borrowed(s));
+    }
+    let mut s = s.clone();
+    resolve_statements(ps, std::slice::from_mut(&mut s))?;
+    Ok(Cow::Owned(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        compile, for_each_expr, has_placeholders, parse_and_resolve_mixed, placeholder_names,
+        referenced_tables, render_statement, resolve_all, resolve_all_checked, resolve_all_collect,
+        resolve_all_limited, resolve_all_scalars, resolve_all_strict, resolve_all_with_options,
+        resolve_cloned, split_parameters_by_statement, substitute_placeholder, to_driver_syntax,
+        DriverSyntax, Error, FragmentComposer, NamedParameterSet, Number, ParameterSet,
+        RenderLimits, RenderOptions, Resolver, Value, ValueKind,
+    };
+    use sqlparser::{
+        ast::{Expr, Ident, SelectItem, SetExpr, Statement, Value as AstValue},
+        dialect::{
+            Dialect, DuckDbDialect, GenericDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+            SnowflakeDialect,
+        },
+        parser::Parser,
+    };
+    use std::{borrow::Cow, time::Duration};
+
+    #[test]
+    fn sql_parsing_resolving() {
+        let sql = "create table test(x int, y int, title varchar);
+insert into test (x, y, title) values($1, $2, $3);
+select $1 px, t.* from test t;";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let mut ps = ParameterSet::default();
+        ps.add(123.into());
         ps.add(456.into());
         ps.add("Hell!".into());
 
@@ -304,4 +1307,2095 @@ select $1 px, t.* from test t;";
             println!("{}", statement);
         }
     }
+
+    #[test]
+    fn resolving_map_keys_and_values() {
+        let sql = "select map {$2: $1, 'k': $1}";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let mut ps = ParameterSet::default();
+        ps.add(1.into());
+        ps.add("key".into());
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "SELECT MAP {'key': 1, 'k': 1}");
+    }
+
+    #[test]
+    fn resolving_wildcard_replace() {
+        let sql = "select * replace ($1 as a), t.* replace ($2 + 1 as b) from t";
+        let dialect = DuckDbDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["x".into(), 2.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * REPLACE ('x' AS a), t.* REPLACE (2 + 1 AS b) FROM t"
+        );
+    }
+
+    #[test]
+    fn resolving_subscripts_on_functions() {
+        let sql = "select data['key'][$1], my_func($2)['field'], arr[$3:$4] from t";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "x".into(), 2.into(), 3.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT data['key'][1], my_func('x')['field'], arr[2:3] FROM t"
+        );
+
+        let sql = "select data['key'][$1], my_func($2)['field'] from t";
+        let mut rs = Parser::parse_sql(&DuckDbDialect {}, sql).unwrap();
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT data['key'][1], my_func('x')['field'] FROM t"
+        );
+    }
+
+    #[test]
+    fn resolving_dictionary_fields() {
+        let sql = "select {'a': $1, 'b': $2}";
+        let dialect = DuckDbDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "two".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "SELECT {'a': 1, 'b': 'two'}");
+    }
+
+    #[test]
+    fn resolving_cloned_borrows_static_sql() {
+        let sql = "select 1 from test; select $1 from test";
+        let dialect = GenericDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![7.into()]);
+
+        assert!(!has_placeholders(&rs[0]));
+        assert!(has_placeholders(&rs[1]));
+        assert!(matches!(resolve_cloned(&ps, &rs[0]), Ok(Cow::Borrowed(_))));
+        let resolved = resolve_cloned(&ps, &rs[1]).unwrap();
+        assert!(matches!(resolved, Cow::Owned(_)));
+        assert_eq!(resolved.to_string(), "SELECT 7 FROM test");
+        assert_eq!(rs[1].to_string(), "SELECT $1 FROM test");
+    }
+
+    #[test]
+    fn resolving_analyze_partitions() {
+        let sql = "analyze table t partition (p = $1) compute statistics";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["2024-01".into()]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "ANALYZE TABLE t PARTITION (p = '2024-01') COMPUTE STATISTICS"
+        );
+    }
+
+    #[test]
+    fn resolving_decimal_with_scale() {
+        let sql = "insert into prices (a, b) values ($1, $2)";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![
+            Value::decimal_with_scale(10.into(), 2),
+            Value::decimal_with_scale("3.14159".parse().unwrap(), 3),
+        ]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "INSERT INTO prices (a, b) VALUES (10.00, 3.142)"
+        );
+    }
+
+    #[test]
+    fn resolving_pagination() {
+        let sql = "select * from t order by $1 limit $2 offset $3";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from(vec![2.into(), 20.into(), 40.into()]);
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t ORDER BY 2 LIMIT 20 OFFSET 40"
+        );
+
+        let sql = "select * from t offset $1 rows fetch first $2 rows only";
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from(vec![40.into(), 20.into()]);
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t OFFSET 40 ROWS FETCH FIRST 20 ROWS ONLY"
+        );
+    }
+
+    #[test]
+    fn resolving_exists_with_cte_union() {
+        let sql = "select * from t where exists (with x as (select id from u where k = $1) \
+select id from x where id = t.id union select $2) and t.y = $3";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["k".into(), 0.into(), 3.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE EXISTS (WITH x AS (SELECT id FROM u WHERE k = 'k') \
+SELECT id FROM x WHERE id = t.id UNION SELECT 0) AND t.y = 3"
+        );
+    }
+
+    #[test]
+    fn resolving_nested_unions() {
+        let sql = "select $1 union all select $2 union select $3";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), 2.into(), 3.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT 1 UNION ALL SELECT 2 UNION SELECT 3"
+        );
+    }
+
+    #[test]
+    fn resolving_set_operation_with_limit() {
+        let sql = "select $1 union select $2 order by 1 limit $3 offset $4";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["a".into(), "b".into(), 10.into(), 5.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT 'a' UNION SELECT 'b' ORDER BY 1 LIMIT 10 OFFSET 5"
+        );
+    }
+
+    #[test]
+    fn resolving_recursive_cte() {
+        let sql = "with recursive c (n) as (select $1 union all select n + 1 from c where n < $2) \
+select * from c";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), 10.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "WITH RECURSIVE c (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM c WHERE n < 10) \
+SELECT * FROM c"
+        );
+    }
+
+    #[test]
+    fn resolving_cte_and_outer_query() {
+        let sql = "with recent as (select * from t where created > $1) \
+select * from recent where id = $2";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["2024-01-01".into(), 7.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "WITH recent AS (SELECT * FROM t WHERE created > '2024-01-01') \
+SELECT * FROM recent WHERE id = 7"
+        );
+    }
+
+    #[test]
+    fn resolving_materialized_ctes() {
+        let sql = "with a as materialized (select * from t where x = $1), \
+b as not materialized (select * from u where y = $2) select * from a, b";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), 2.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "WITH a AS MATERIALIZED (SELECT * FROM t WHERE x = 1), \
+b AS NOT MATERIALIZED (SELECT * FROM u WHERE y = 2) SELECT * FROM a, b"
+        );
+    }
+
+    #[test]
+    fn resolving_column_aliased_cte() {
+        let sql = "with c(a, b) as (values ($1, $2)) select * from c";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "one".into()]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "WITH c (a, b) AS (VALUES (1, 'one')) SELECT * FROM c"
+        );
+    }
+
+    #[test]
+    fn resolving_checked_kinds() {
+        let sql = "select $1 + 1 from test where name like $2";
+        let dialect = GenericDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "a%".into()]);
+        let mut ok = rs.clone();
+        resolve_all_checked(&ps, &mut ok).unwrap();
+        assert_eq!(
+            ok[0].to_string(),
+            "SELECT 1 + 1 FROM test WHERE name LIKE 'a%'"
+        );
+
+        let ps = ParameterSet::from(vec!["1".into(), "a%".into()]);
+        let mut bad = rs.clone();
+        let r = resolve_all_checked(&ps, &mut bad);
+        assert!(matches!(r, Err(Error::TypeMismatch(_))));
+
+        let mut rs = Parser::parse_sql(&dialect, "select upper($1)").unwrap();
+        let ps = ParameterSet::from(vec![1.into()]);
+        let r = resolve_all_checked(&ps, &mut rs);
+        assert!(matches!(r, Err(Error::TypeMismatch(_))));
+
+        let sql = "select case $1 when 'a' then 0 when 'b' then 1 end";
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from(vec![1.into()]);
+        let r = resolve_all_checked(&ps, &mut rs.clone());
+        assert!(matches!(r, Err(Error::TypeMismatch(_))));
+        let ps = ParameterSet::from(vec!["a".into()]);
+        resolve_all_checked(&ps, &mut rs.clone()).unwrap();
+
+        let sql = "select upper(?), ? + 1";
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from(vec!["a".into(), 1.into()]);
+        let mut ok = rs.clone();
+        resolve_all_checked(&ps, &mut ok).unwrap();
+        assert_eq!(ok[0].to_string(), "SELECT upper('a'), 1 + 1");
+        let ps = ParameterSet::from(vec![1.into(), "a".into()]);
+        let r = resolve_all_checked(&ps, &mut rs.clone());
+        assert!(matches!(r, Err(Error::TypeMismatch(m)) if m.starts_with("?1 ")));
+
+        let rs = Parser::parse_sql(&dialect, "select $1 + 1 where $2 like 'a%'").unwrap();
+        let ps = ParameterSet::from(vec![
+            Value::typed_null("int").unwrap(),
+            Value::typed_null("text").unwrap(),
+        ]);
+        let mut nulls = rs.clone();
+        resolve_all_checked(&ps, &mut nulls).unwrap();
+        assert_eq!(
+            nulls[0].to_string(),
+            "SELECT CAST(NULL AS INT) + 1 WHERE CAST(NULL AS TEXT) LIKE 'a%'"
+        );
+    }
+
+    #[test]
+    fn resolving_show_filters() {
+        let sql = "show tables where name = $1; show columns from test where field <> $2";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["test".into(), "id".into()]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "SHOW TABLES WHERE name = 'test'");
+        assert_eq!(
+            rs[1].to_string(),
+            "SHOW COLUMNS FROM test WHERE field <> 'id'"
+        );
+    }
+
+    #[test]
+    fn value_loose_eq() {
+        let one = Value::from(1);
+        assert!(one.loose_eq(&"1".into()));
+        assert!(one.loose_eq(&"1.00".into()));
+        assert!(Value::from("1.0").loose_eq(&one));
+        assert!(!one.loose_eq(&"one".into()));
+        assert!(!Value::from("1").loose_eq(&"1.0".into()));
+        assert_ne!(one, Value::from("1"));
+    }
+
+    #[test]
+    fn resolving_table_function_args() {
+        let sql = "select * from json_to_recordset($1) with ordinality as x(a, b)";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![r#"[{"a":1,"b":"x"}]"#.into()]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            r#"SELECT * FROM json_to_recordset('[{"a":1,"b":"x"}]') WITH ORDINALITY AS x (a, b)"#
+        );
+
+        let sql = "select * from json_to_recordset($1) as x(a, b) where x.a > $2";
+        let ps = ParameterSet::from(vec!["[]".into(), 0.into()]);
+        assert_eq!(
+            Resolver::new(&dialect).resolve(sql, &ps).unwrap(),
+            "SELECT * FROM json_to_recordset('[]') AS x (a, b) WHERE x.a > 0"
+        );
+
+        // sqlparser 0.51 doesn't parse a column definition list with types.
+        let sql = "select * from json_to_recordset($1) as x(a int, b text)";
+        let r = Resolver::new(&dialect).resolve(sql, &ps);
+        assert!(matches!(r, Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn binding_compiled_template() {
+        let dialect = GenericDialect {};
+        let template = compile(&dialect, "select $1, $2 from t where a = $1").unwrap();
+        assert_eq!(template.placeholder_count(), 2);
+
+        let ps = ParameterSet::from(vec![1.into(), "x".into()]);
+        let rs = template.bind(&ps).unwrap();
+        assert_eq!(rs[0].to_string(), "SELECT 1, 'x' FROM t WHERE a = 1");
+
+        let ps = ParameterSet::from(vec![2.into(), "y".into()]);
+        let rs = template.bind(&ps).unwrap();
+        assert_eq!(rs[0].to_string(), "SELECT 2, 'y' FROM t WHERE a = 2");
+        assert_eq!(
+            template.statements()[0].to_string(),
+            "SELECT $1, $2 FROM t WHERE a = $1"
+        );
+
+        let template = compile(&dialect, "select ?, ?, ?").unwrap();
+        assert_eq!(template.placeholder_count(), 3);
+        let template = compile(&dialect, "select :a, ?, :a, ?").unwrap();
+        assert_eq!(template.placeholder_count(), 3);
+
+        assert!(matches!(
+            compile(&dialect, "select from where"),
+            Err(Error::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn resolving_mixed_dialects() {
+        let ps = ParameterSet::from(vec![1.into(), "x".into()]);
+        let inputs: Vec<(Box<dyn Dialect>, &str)> = vec![
+            (Box::new(PostgreSqlDialect {}), "select $1::int"),
+            (Box::new(SQLiteDialect {}), "select [a] from t where b = $2"),
+        ];
+        let rs = parse_and_resolve_mixed(&inputs, &ps).unwrap();
+        assert_eq!(rs[0].to_string(), "SELECT 1::INT");
+        assert_eq!(rs[1].to_string(), "SELECT [a] FROM t WHERE b = 'x'");
+
+        let inputs: Vec<(Box<dyn Dialect>, &str)> = vec![
+            (Box::new(PostgreSqlDialect {}), "select 1"),
+            (Box::new(MySqlDialect {}), "select from where"),
+        ];
+        let r = parse_and_resolve_mixed(&inputs, &ps);
+        assert!(matches!(r, Err(Error::Parse(e)) if e.starts_with("fragment 1:")));
+    }
+
+    #[test]
+    fn composing_fragments() {
+        let dialect = GenericDialect {};
+        let (mut statement, ps) = FragmentComposer::new(&dialect)
+            .with(
+                "a",
+                "select id from users where age > $1 and name <> $2",
+                ParameterSet::from(vec![18.into(), "root".into()]),
+            )
+            .unwrap()
+            .finish(
+                "select * from orders where user_id in (select id from a) and total > $1",
+                ParameterSet::from(vec![100.into()]),
+            )
+            .unwrap();
+        assert_eq!(
+            statement.to_string(),
+            "WITH a AS (SELECT id FROM users WHERE age > $1 AND name <> $2) \
+SELECT * FROM orders WHERE user_id IN (SELECT id FROM a) AND total > $3"
+        );
+
+        let mut rs = vec![statement.clone()];
+        resolve_all(&ps, &mut rs).unwrap();
+        statement = rs.remove(0);
+        assert_eq!(
+            statement.to_string(),
+            "WITH a AS (SELECT id FROM users WHERE age > 18 AND name <> 'root') \
+SELECT * FROM orders WHERE user_id IN (SELECT id FROM a) AND total > 100"
+        );
+
+        let r = FragmentComposer::new(&dialect).with(
+            "a",
+            "select $2",
+            ParameterSet::from(vec![1.into()]),
+        );
+        assert!(matches!(r, Err(Error::Collision(_))));
+    }
+
+    #[test]
+    fn resolving_on_conflict() {
+        let sql = "insert into t (a, b) values ($1, $2) \
+on conflict (a) do update set b = $2 where t.b < $3";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), 2.into(), 3.into()]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "INSERT INTO t (a, b) VALUES (1, 2) ON CONFLICT(a) DO UPDATE SET b = 2 WHERE t.b < 3"
+        );
+    }
+
+    #[test]
+    fn resolving_vec_and_hashmap_values() {
+        let dialect = DuckDbDialect {};
+        let mut rs = Parser::parse_sql(&dialect, "select $1, $2").unwrap();
+
+        let map: std::collections::HashMap<&str, i32> = [("b", 2), ("a", 1)].into();
+        let ps = ParameterSet::from(vec![vec![1, 2, 3].into(), map.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT ARRAY[1, 2, 3], MAP {'a': 1, 'b': 2}"
+        );
+    }
+
+    #[test]
+    fn resolving_optional_values() {
+        let sql = "select * from t where a = $1 and b is not distinct from $2";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let mut ps = ParameterSet::default();
+        ps.add(Some(5i32).into());
+        ps.add(None::<i32>.into());
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE a = 5 AND b IS NOT DISTINCT FROM NULL"
+        );
+    }
+
+    #[test]
+    fn resolving_typed_null() {
+        let sql = "select $1, $2";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![Value::typed_null("int").unwrap(), Value::Null]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "SELECT CAST(NULL AS INT), NULL");
+    }
+
+    #[test]
+    fn resolving_case_with_subqueries() {
+        let sql = "select case when col in (select $1) then (select $2) \
+else (select u.x from u where u.id = t.id and u.y = $3) end from t";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "two".into(), 3.into()]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT CASE WHEN col IN (SELECT 1) THEN (SELECT 'two') \
+ELSE (SELECT u.x FROM u WHERE u.id = t.id AND u.y = 3) END FROM t"
+        );
+    }
+
+    #[test]
+    fn resolving_merge_clauses() {
+        let sql = "merge into t using (select * from s where k = $1) src on t.id = src.id \
+when matched and src.v > $2 then update set v = $3 \
+when not matched then insert (id, v) values (src.id, $4)";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["a".into(), 1.into(), 2.into(), 3.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "MERGE INTO t USING (SELECT * FROM s WHERE k = 'a') AS src ON t.id = src.id \
+WHEN MATCHED AND src.v > 1 THEN UPDATE SET v = 2 \
+WHEN NOT MATCHED THEN INSERT (id, v) VALUES (src.id, 3)"
+        );
+    }
+
+    #[test]
+    fn resolving_merge_values_source() {
+        let sql =
+            "merge into t using (values ($1, $2)) as src (id, v) on t.id = src.id and t.k = $3 \
+when matched then update set v = src.v, updated = $4";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "x".into(), "k".into(), true.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "MERGE INTO t USING (VALUES (1, 'x')) AS src (id, v) ON t.id = src.id AND t.k = 'k' \
+WHEN MATCHED THEN UPDATE SET v = src.v, updated = true"
+        );
+    }
+
+    #[test]
+    fn resolving_array_operators() {
+        let sql = "select * from t where tags && $1 and tags @> $2 and tags <@ $3 \
+and tags || $4 = tags";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let array = |xs: &[&str]| Value::Array(xs.iter().map(|&x| x.into()).collect());
+        let ps = ParameterSet::from(vec![
+            array(&["a", "b"]),
+            array(&["a"]),
+            array(&["a", "b", "c"]),
+            array(&[]),
+        ]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE tags && ARRAY['a', 'b'] AND tags @> ARRAY['a'] \
+AND tags <@ ARRAY['a', 'b', 'c'] AND tags || ARRAY[] = tags"
+        );
+        // Valid Postgres: it parses back to the same statement.
+        let sql = rs[0].to_string();
+        assert_eq!(Parser::parse_sql(&dialect, &sql).unwrap(), rs);
+    }
+
+    #[test]
+    fn resolving_row_comparison() {
+        let sql = "select * from t where (a, b) = ($1, $2) and (a, b) < ($3, ($4 + 1))";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "x".into(), 2.into(), 3.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE (a, b) = (1, 'x') AND (a, b) < (2, (3 + 1))"
+        );
+    }
+
+    #[test]
+    fn resolving_values_in_subquery() {
+        let dialect = PostgreSqlDialect {};
+        let mut rs =
+            Parser::parse_sql(&dialect, "select * from t where (a, b) in (select 1)").unwrap();
+        // The parser only treats SELECT/WITH as an IN subquery, so splice
+        // a VALUES body in by hand.
+        let values = Parser::parse_sql(&dialect, "values ($1, $2), ($3, $4)").unwrap();
+        let (Statement::Query(body), Statement::Query(q)) = (&values[0], &mut rs[0]) else {
+            unreachable!()
+        };
+        let SetExpr::Select(select) = q.body.as_mut() else {
+            unreachable!()
+        };
+        let Some(Expr::InSubquery { subquery, .. }) = select.selection.as_mut() else {
+            unreachable!()
+        };
+        *subquery = body.clone();
+
+        let ps = ParameterSet::from(vec![1.into(), "x".into(), 2.into(), "y".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE (a, b) IN (VALUES (1, 'x'), (2, 'y'))"
+        );
+
+        // As written in SQL, the rows parse as an IN list of tuples.
+        let sql = "select * from t where (a, b) in (($1, $2), ($3, $4))";
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE (a, b) IN ((1, 'x'), (2, 'y'))"
+        );
+    }
+
+    #[test]
+    fn resolving_anonymous_placeholders() {
+        let sql = "insert into t(a, b) values(?, ?)";
+        let dialect = MySqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "b".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "INSERT INTO t (a, b) VALUES (1, 'b')");
+
+        let sql = "select ?, case ? when 1 then ? else ? end from t where a = ? limit ?";
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from((1..=6).map(Value::from).collect());
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT 1, CASE 2 WHEN 1 THEN 3 ELSE 4 END FROM t WHERE a = 5 LIMIT 6"
+        );
+
+        let mut rs = Parser::parse_sql(&GenericDialect {}, "select ?, $2").unwrap();
+        let r = resolve_all(&ps, &mut rs);
+        assert!(matches!(r, Err(Error::Positional(_))));
+    }
+
+    #[test]
+    fn error_exposes_json_source() {
+        use std::error::Error as _;
+
+        let e: Error = serde_json::from_str::<Value>("{").unwrap_err().into();
+        assert!(e.source().is_some());
+        assert!(e.to_string().starts_with("json: "));
+        let boxed: Box<dyn std::error::Error> = Box::new(Error::Notfound("$1".into()));
+        assert!(boxed.source().is_none());
+    }
+
+    #[test]
+    fn resolving_collects_all_errors() {
+        let sql = "select $1; select $5; select $2; select $9";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), 2.into()]);
+
+        let errors = resolve_all_collect(&ps, &mut rs).unwrap_err();
+        let indices: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![1, 3]);
+        let sql: Vec<String> = rs.iter().map(|s| s.to_string()).collect();
+        assert_eq!(sql, vec!["SELECT 1", "SELECT $5", "SELECT 2", "SELECT $9"]);
+
+        let sql = "select ?; select ?, :x; select ?";
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from(vec![1.into(), 2.into(), 3.into()]);
+
+        let errors = resolve_all_collect(&ps, &mut rs).unwrap_err();
+        let indices: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![1]);
+        let sql: Vec<String> = rs.iter().map(|s| s.to_string()).collect();
+        assert_eq!(sql, vec!["SELECT 1", "SELECT ?, :x", "SELECT 3"]);
+    }
+
+    #[test]
+    fn resolving_anonymous_in_source_order() {
+        let sql = "WITH c AS (SELECT ? AS x) INSERT INTO t SELECT ?, x FROM c";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), 2.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "WITH c AS (SELECT 1 AS x) INSERT INTO t SELECT 2, x FROM c"
+        );
+
+        let mut rs = Parser::parse_sql(&dialect, "explain select ?, ?").unwrap();
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "EXPLAIN SELECT 1, 2");
+    }
+
+    #[test]
+    fn resolving_named_parameters() {
+        let sql = "select * from t where id = :id or parent = :id and kind = :kind";
+        let dialect = PostgreSqlDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = NamedParameterSet::default()
+            .bind("id", 7.into())
+            .bind("kind", "leaf".into());
+
+        let mut resolved = rs.clone();
+        resolve_all_strict(&ps, &mut resolved).unwrap();
+        assert_eq!(
+            resolved[0].to_string(),
+            "SELECT * FROM t WHERE id = 7 OR parent = 7 AND kind = 'leaf'"
+        );
+
+        let mut positional = rs.clone();
+        let values = ps.to_positional(&mut positional).unwrap();
+        assert_eq!(
+            positional[0].to_string(),
+            "SELECT * FROM t WHERE id = $1 OR parent = $1 AND kind = $2"
+        );
+        assert_eq!(values.values, vec![7.into(), "leaf".into()]);
+
+        let ps = NamedParameterSet::default().bind("id", 7.into());
+        let r = ps.to_positional(&mut rs.clone());
+        assert_eq!(r.err(), Some(Error::Notfound(":kind".into())));
+    }
+
+    #[test]
+    fn resolving_mixed_sigil_names() {
+        let sql = "select * from t where id = :id and name = @name";
+        let dialect = SQLiteDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let mut ps = NamedParameterSet::default();
+        ps.add_named("id", 1.into());
+        ps.add_named("name", "n".into());
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE id = 1 AND name = 'n'"
+        );
+    }
+
+    #[test]
+    fn deduplicating_parameter_set() {
+        let ps = ParameterSet::from(vec![1.into(), "a".into(), 1.into(), "a".into(), 2.into()]);
+        let (deduped, remap) = ps.dedup();
+        assert_eq!(deduped.values, vec![1.into(), "a".into(), 2.into()]);
+        assert_eq!(remap, vec![0, 1, 0, 1, 2]);
+
+        let ten = || Number::from(10);
+        let ps = ParameterSet::from(vec![
+            Value::from(10),
+            Value::Number("10.0".parse().unwrap()),
+            Value::decimal_with_scale(ten(), 2),
+            Value::decimal_with_scale(ten(), 2),
+            Value::Array(vec![Value::decimal_with_scale(ten(), 1)]),
+            Value::Array(vec![10.into()]),
+        ]);
+        let (deduped, remap) = ps.dedup();
+        let rendered: Vec<String> = deduped.values.iter().map(|v| v.to_string()).collect();
+        assert_eq!(
+            rendered,
+            vec!["10", "10.0", "10.00", "ARRAY[10.0]", "ARRAY[10]"]
+        );
+        assert_eq!(remap, vec![0, 1, 2, 2, 3, 4]);
+    }
+
+    #[test]
+    fn validating_contiguous_positions() {
+        let dialect = GenericDialect {};
+        let ps = ParameterSet::from(vec![1.into(), 2.into()]);
+
+        let rs = Parser::parse_sql(&dialect, "select $1, $2; select $2, :name").unwrap();
+        ps.validate_contiguous(&rs).unwrap();
+
+        let rs = Parser::parse_sql(&dialect, "select $1, $3").unwrap();
+        let r = ps.validate_contiguous(&rs);
+        assert_eq!(r, Err(Error::Positional("$2 not referenced".into())));
+
+        let rs = Parser::parse_sql(&dialect, "select $1, $2, $3").unwrap();
+        let r = ps.validate_contiguous(&rs);
+        assert!(matches!(r, Err(Error::Positional(_))));
+
+        let rs = Parser::parse_sql(&dialect, "select ?; select ?").unwrap();
+        ps.validate_contiguous(&rs).unwrap();
+
+        let rs = Parser::parse_sql(&dialect, "select ?").unwrap();
+        let r = ps.validate_contiguous(&rs);
+        assert_eq!(r, Err(Error::Positional("$2 not referenced".into())));
+
+        let rs = Parser::parse_sql(&dialect, "select ?, $1").unwrap();
+        let r = ps.validate_contiguous(&rs);
+        assert!(matches!(r, Err(Error::Positional(_))));
+    }
+
+    #[test]
+    fn rendering_driver_syntax() {
+        let sql = "select * from t where a = $2 and b = $1 and c = $2";
+        let dialect = PostgreSqlDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from(vec!["one".into(), "two".into()]);
+
+        let cases = [
+            (DriverSyntax::Dollar, "a = $1 AND b = $2 AND c = $1", 2),
+            (DriverSyntax::Colon, "a = :1 AND b = :2 AND c = :1", 2),
+            (DriverSyntax::AtP, "a = @p1 AND b = @p2 AND c = @p1", 2),
+            (DriverSyntax::Question, "a = ? AND b = ? AND c = ?", 3),
+        ];
+        for (syntax, filter, count) in cases {
+            let mut rendered = rs.clone();
+            let values = to_driver_syntax(&ps, &mut rendered, syntax).unwrap();
+            assert_eq!(
+                rendered[0].to_string(),
+                format!("SELECT * FROM t WHERE {}", filter)
+            );
+            assert_eq!(values.len(), count);
+            assert_eq!(values[0], Value::from("two"));
+            assert_eq!(values[1], Value::from("one"));
+        }
+
+        let mut rs = Parser::parse_sql(&MySqlDialect {}, "select ?, ?").unwrap();
+        let values = to_driver_syntax(&ps, &mut rs, DriverSyntax::Dollar).unwrap();
+        assert_eq!(rs[0].to_string(), "SELECT $1, $2");
+        assert_eq!(values, vec![Value::from("one"), Value::from("two")]);
+
+        // CASE conditions come before their results in the text, and the
+        // values follow the text rather than the AST's field order.
+        let sql = "select case when a = $1 then $2 when b = $3 then $4 end";
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from(vec![1.into(), 2.into(), 3.into(), 4.into()]);
+        let values = to_driver_syntax(&ps, &mut rs, DriverSyntax::Question).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT CASE WHEN a = ? THEN ? WHEN b = ? THEN ? END"
+        );
+        let expected: Vec<Value> = vec![1.into(), 2.into(), 3.into(), 4.into()];
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn splitting_parameters_by_statement() {
+        let sql = "select ?, ?; select 1; update t set a = ? where b = ? and c = ?";
+        let dialect = GenericDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        assert_eq!(split_parameters_by_statement(&rs), vec![0..2, 2..2, 2..5]);
+
+        // `SET` values aren't resolved, so its `?` takes no value.
+        let mut rs = Parser::parse_sql(&dialect, "select ?; set x = ?; select ?").unwrap();
+        let ranges = split_parameters_by_statement(&rs);
+        assert_eq!(ranges, vec![0..1, 1..1, 1..2]);
+        resolve_all(&ParameterSet::from(vec![1.into(), 2.into()]), &mut rs).unwrap();
+        assert_eq!(rs[2].to_string(), "SELECT 2");
+    }
+
+    #[test]
+    fn listing_placeholder_names() {
+        let sql = "select $1, :name, ? from t where a = :name and b = $1";
+        let dialect = GenericDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let names: Vec<String> = placeholder_names(&rs[0]).into_iter().collect();
+        assert_eq!(names, vec!["$1", ":name", "?"]);
+    }
+
+    #[test]
+    fn listing_referenced_tables() {
+        let sql = "with recent as (select * from orders where ts > $1) \
+select * from recent r join app.users u on u.id = r.user_id \
+where u.id in (select user_id from bans)";
+        let dialect = PostgreSqlDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let tables: Vec<String> = referenced_tables(&rs[0]).into_iter().collect();
+        assert_eq!(tables, vec!["app.users", "bans", "orders"]);
+    }
+
+    #[test]
+    fn referenced_tables_cte_shadowing() {
+        let dialect = PostgreSqlDialect {};
+        let cases = [
+            (
+                "with secret as (select * from secret) select * from secret",
+                vec!["secret"],
+            ),
+            (
+                "select * from secret where id in (with secret as (select 1) select * from secret)",
+                vec!["secret"],
+            ),
+            (
+                "with a as (select * from b), b as (select 1) select * from a",
+                vec!["b"],
+            ),
+            (
+                "with a as (select 1), b as (select * from a) select * from b",
+                vec![],
+            ),
+            (
+                "with recursive r as (select 1 union all select * from r) select * from r",
+                vec![],
+            ),
+        ];
+        for (sql, expected) in cases {
+            let rs = Parser::parse_sql(&dialect, sql).unwrap();
+            let tables: Vec<String> = referenced_tables(&rs[0]).into_iter().collect();
+            assert_eq!(tables, expected, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn resolving_scalars_only() {
+        let sql = "select * from t where id = any($1) and status = $2 and tags @> $3";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![
+            Value::Array(vec![1.into(), 2.into()]),
+            "open".into(),
+            Value::Dict(vec![("k".into(), "v".into())]),
+        ]);
+
+        let deferred = resolve_all_scalars(&ps, &mut rs).unwrap();
+        assert_eq!(deferred, vec![1, 3]);
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE id = ANY($1) AND status = 'open' AND tags @> $2"
+        );
+
+        let sql = "select * from t where tags @> $3 and status = $2 and id = any($1) \
+or parent = any($1)";
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let deferred = resolve_all_scalars(&ps, &mut rs).unwrap();
+        assert_eq!(deferred, vec![3, 1]);
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE tags @> $1 AND status = 'open' AND id = ANY($2) \
+OR parent = ANY($2)"
+        );
+
+        let sql = "select ?, ?, ?";
+        let mut rs = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        let deferred = resolve_all_scalars(&ps, &mut rs).unwrap();
+        assert_eq!(deferred, vec![1, 3]);
+        assert_eq!(rs[0].to_string(), "SELECT ?, 'open', ?");
+
+        let sql = "select * from t where a = any($1) and b > all($1)";
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE a = ANY(ARRAY[1, 2]) AND b > ALL(ARRAY[1, 2])"
+        );
+    }
+
+    #[test]
+    fn resolving_with_resolver() {
+        let dialect = PostgreSqlDialect {};
+        let ps = ParameterSet::from(vec![1.into(), Value::Null]);
+
+        let sql = Resolver::new(&dialect)
+            .strict(true)
+            .options(RenderOptions {
+                annotate_bindings: true,
+                null_as: Some("int".into()),
+                ..Default::default()
+            })
+            .resolve("select $1; select * from t where a = $2", &ps)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT 1 /* $1 = 1 */; \
+SELECT * FROM t WHERE a = CAST(NULL AS INT) /* $2 = CAST(NULL AS INT) */"
+        );
+
+        let rs = Resolver::new(&dialect)
+            .resolve_statements("set x = $1", &ps)
+            .unwrap();
+        assert_eq!(rs[0].to_string(), "SET x = $1");
+
+        // Postgres reads `X'..'` as a bit string, so bytes go in as bytea.
+        let ps = ParameterSet::from(vec![Value::Bytes(vec![0xab, 0x01])]);
+        let sql = Resolver::new(&dialect).resolve("select $1", &ps).unwrap();
+        assert_eq!(sql, "SELECT BYTEA '\\xAB01'");
+        let reparsed = Parser::parse_sql(&dialect, &sql).unwrap();
+        assert_eq!(reparsed[0].to_string(), sql);
+        let sql = Resolver::new(&GenericDialect {})
+            .resolve("select $1", &ps)
+            .unwrap();
+        assert_eq!(sql, "SELECT X'AB01'");
+
+        let strict = Resolver::new(&dialect).strict(true);
+        assert!(matches!(
+            strict.resolve("set x = $1", &ps),
+            Err(Error::Unresolved(_))
+        ));
+        let checked = Resolver::new(&dialect).checked(true);
+        assert!(matches!(
+            checked.resolve("select upper($1)", &ps),
+            Err(Error::TypeMismatch(_))
+        ));
+        let limited = Resolver::new(&dialect).limits(RenderLimits {
+            max_value_size: None,
+            max_statement_size: Some(10),
+        });
+        assert!(matches!(
+            limited.resolve("select $1 from t", &ps),
+            Err(Error::TooLarge(_))
+        ));
+        let shallow = Resolver::new(&dialect).max_depth(3);
+        assert!(matches!(
+            shallow.resolve("select ((((($1)))))", &ps),
+            Err(Error::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn resolving_strict_surfaces_leftovers() {
+        let dialect = GenericDialect {};
+        let ps = ParameterSet::from(vec![1.into()]);
+
+        let mut rs = Parser::parse_sql(&dialect, "select $1").unwrap();
+        resolve_all_strict(&ps, &mut rs).unwrap();
+
+        let mut rs = Parser::parse_sql(&dialect, "set x = $1").unwrap();
+        let r = resolve_all_strict(&ps, &mut rs);
+        assert!(matches!(r, Err(Error::Unresolved(_))));
+    }
+
+    #[test]
+    fn visiting_each_expr() {
+        let sql = "select a + 1, upper(b) from t where c = $1";
+        let dialect = GenericDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let mut exprs = Vec::new();
+        for_each_expr(&rs[0], |x| exprs.push(x.to_string()));
+        assert_eq!(
+            exprs,
+            vec!["a + 1", "a", "1", "upper(b)", "b", "c = $1", "c", "$1"]
+        );
+    }
+
+    #[test]
+    fn resolving_cast_inner_expr() {
+        let sql = "select cast($1 as text), try_cast($2 as int)";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![42.into(), "7".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT CAST(42 AS TEXT), TRY_CAST('7' AS INT)"
+        );
+    }
+
+    #[test]
+    fn resolving_cast_array_elements() {
+        let sql = "select cast(array[$1] as int[]), array[$2, $3]::text[]";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "a".into(), "b".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT CAST(ARRAY[1] AS INT[]), ARRAY['a', 'b']::TEXT[]"
+        );
+    }
+
+    #[test]
+    fn resolving_cast_collapses_same_type() {
+        let sql = "select cast($1 as date), cast($2 as date), cast($3 as int)";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![
+            Value::TypedString("date".into(), "2024-01-02".into()),
+            "2024-01-03".into(),
+            Value::TypedString("date".into(), "2024-01-04".into()),
+        ]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT DATE '2024-01-02', CAST('2024-01-03' AS DATE), CAST(DATE '2024-01-04' AS INT)"
+        );
+    }
+
+    #[test]
+    fn resolving_bytes_value() {
+        let sql = "insert into t (data) values ($1)";
+        let dialect = SQLiteDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![Value::from(&b"\x00'\xff"[..])]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "INSERT INTO t (data) VALUES (X'0027FF')");
+
+        let v = Value::Bytes(vec![0u8, 1, 254, 255]);
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(json, r#"{"Bytes":"AAH+/w=="}"#);
+        assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), v);
+
+        // An owned Vec<u8> is the JSON wire form, not binary data.
+        let wire: Vec<u8> = (&v).into();
+        assert_eq!(wire, json.as_bytes());
+        assert_eq!(Value::try_from(wire).unwrap(), v);
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn parameter_set_binary_round_trip() {
+        let ps = ParameterSet::from(vec![
+            true.into(),
+            1.into(),
+            Value::Number("-12345678901234567890.000123".parse().unwrap()),
+            "text".into(),
+            Value::TypedString("date".into(), "2024-01-02".into()),
+            Value::Array(vec![1.into(), Value::Array(vec!["a".into()])]),
+            Value::Dict(vec![("k".into(), Value::Null)]),
+            Value::Null,
+            Value::typed_null("int").unwrap(),
+            Value::Bytes(vec![0, 0xff]),
+        ]);
+
+        let bytes = ps.to_bytes().unwrap();
+        let back = ParameterSet::from_bytes(&bytes).unwrap();
+        assert_eq!(back.values, ps.values);
+        assert!(ParameterSet::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn resolving_long_and_chain() {
+        // sqlparser's own Display/Visit/Drop recurse along the left-deep
+        // AND tree, which needs more than the default 2MiB test stack in
+        // debug builds; give this thread a fixed, generous budget instead.
+        let worker = std::thread::Builder::new().stack_size(32 << 20);
+        let handle = worker
+            .spawn(|| {
+                let n = 1000;
+                let conditions: Vec<String> = (1..=n).map(|i| format!("c{} = ${}", i, i)).collect();
+                let sql = format!("select * from t where {}", conditions.join(" and "));
+                let dialect = GenericDialect {};
+                let mut rs = Parser::parse_sql(&dialect, &sql).unwrap();
+
+                let ps = ParameterSet::from((1..=n).map(Value::from).collect());
+
+                resolve_all_strict(&ps, &mut rs).unwrap();
+                let resolved = rs[0].to_string();
+                assert!(resolved.starts_with("SELECT * FROM t WHERE c1 = 1 AND c2 = 2"));
+                assert!(resolved.ends_with("AND c1000 = 1000"));
+            })
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn resolving_joins_and_derived_tables() {
+        let sql = "select * from a join b on b.k = $1 \
+left join (select * from c where c.x = $2) d on d.id = a.id";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "x".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM a JOIN b ON b.k = 1 \
+LEFT JOIN (SELECT * FROM c WHERE c.x = 'x') AS d ON d.id = a.id"
+        );
+    }
+
+    #[test]
+    fn resolving_leading_derived_table() {
+        let sql = "select * from (select * from c where c.x = $1) s \
+join b using (id) where s.y = $2";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), 2.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM (SELECT * FROM c WHERE c.x = 1) AS s \
+JOIN b USING(id) WHERE s.y = 2"
+        );
+    }
+
+    #[test]
+    fn resolving_update_without_where() {
+        let sql = "update t set x = $1";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "UPDATE t SET x = 1");
+    }
+
+    #[test]
+    fn resolving_update_from_joins() {
+        let sql = "update t set x = $1 from a join b on a.k = b.k and b.flag = $2 \
+where t.id = a.id";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), true.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "UPDATE t SET x = 1 FROM a JOIN b ON a.k = b.k AND b.flag = true WHERE t.id = a.id"
+        );
+    }
+
+    #[test]
+    fn resolving_delete_clauses() {
+        let sql = "delete from t where a = $1 order by $2 limit $3";
+        let mut rs = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        let ps = ParameterSet::from(vec![1.into(), 2.into(), 10.into()]);
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "DELETE FROM t WHERE a = 1 ORDER BY 2 LIMIT 10"
+        );
+
+        let sql = "delete from t where a = $1 returning id, $2 as tag";
+        let mut rs = Parser::parse_sql(&PostgreSqlDialect {}, sql).unwrap();
+        let ps = ParameterSet::from(vec![1.into(), "gone".into()]);
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "DELETE FROM t WHERE a = 1 RETURNING id, 'gone' AS tag"
+        );
+    }
+
+    #[test]
+    fn resolving_insert_default_values() {
+        let dialect = PostgreSqlDialect {};
+        let ps = ParameterSet::from(vec![1.into(), 2.into()]);
+
+        let mut rs = Parser::parse_sql(&dialect, "insert into t default values").unwrap();
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "INSERT INTO t DEFAULT VALUES");
+
+        // Data-modifying CTEs put an INSERT where a query body would be.
+        let sql = "with x as (insert into t values ($1) returning id) \
+select * from x where id > $2";
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "WITH x AS (INSERT INTO t VALUES (1) RETURNING id) SELECT * FROM x WHERE id > 2"
+        );
+    }
+
+    #[test]
+    fn resolving_insert_with_cte() {
+        let sql = "insert into t (a, b) with c as (select $1 as x) select x, $2 from c";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), "b".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "INSERT INTO t (a, b) WITH c AS (SELECT 1 AS x) SELECT x, 'b' FROM c"
+        );
+    }
+
+    #[test]
+    fn resolving_insert_table() {
+        let sql = "insert into t table other";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::default();
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "INSERT INTO t TABLE other");
+    }
+
+    #[test]
+    fn value_to_sql_literal() {
+        assert_eq!(Value::from("it's").to_sql_literal().unwrap(), "'it''s'");
+        assert_eq!(Value::from(42).to_sql_literal().unwrap(), "42");
+        assert_eq!(
+            Value::Array(vec![1.into(), 2.into()])
+                .to_sql_literal()
+                .unwrap(),
+            "ARRAY[1, 2]"
+        );
+        assert_eq!(Value::Null.to_sql_literal().unwrap(), "NULL");
+        assert!(matches!(Value::typed_null(""), Err(Error::Conversion(_))));
+        assert_eq!(
+            Value::typed_null("varchar(10)")
+                .unwrap()
+                .to_sql_literal()
+                .unwrap(),
+            "CAST(NULL AS VARCHAR(10))"
+        );
+
+        let injected = "int) ; drop table t; --";
+        assert!(matches!(
+            Value::typed_null(injected),
+            Err(Error::Conversion(_))
+        ));
+        let injected = Value::TypedNull(injected.into());
+        assert!(matches!(
+            injected.to_sql_literal(),
+            Err(Error::Conversion(_))
+        ));
+        assert_eq!(
+            injected.to_string(),
+            "CAST(NULL AS \"int) ; drop table t; --\")"
+        );
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, "select $1").unwrap();
+        let r = resolve_all(&ParameterSet::from(vec![injected]), &mut rs);
+        assert!(matches!(r, Err(Error::Conversion(_))));
+    }
+
+    #[test]
+    fn value_string_survives_reparse() {
+        let dialect = GenericDialect {};
+        for original in ["\\'; drop table t; --", "a''b", "it's", "\\''\\"] {
+            let v = Value::from(original);
+            let mut rs = Parser::parse_sql(&dialect, "select $1").unwrap();
+            resolve_all(&ParameterSet::from(vec![v.clone()]), &mut rs).unwrap();
+            let sql = rs[0].to_string();
+            assert_eq!(sql, format!("SELECT {}", v));
+
+            let reparsed = Parser::parse_sql(&dialect, &sql).unwrap();
+            assert_eq!(reparsed.len(), 1, "{}", sql);
+            let Statement::Query(q) = &reparsed[0] else {
+                panic!("{}", sql);
+            };
+            let SetExpr::Select(select) = q.body.as_ref() else {
+                panic!("{}", sql);
+            };
+            assert!(
+                matches!(
+                    &select.projection[0],
+                    SelectItem::UnnamedExpr(Expr::Value(AstValue::SingleQuotedString(s)))
+                        if s == original
+                ),
+                "{}",
+                sql
+            );
+        }
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn value_from_rust_decimal() {
+        let d = rust_decimal::Decimal::new(12050, 3);
+        assert_eq!(Value::from(d).to_sql_literal().unwrap(), "12.050");
+    }
+
+    #[test]
+    fn value_array_element_kind() {
+        let a = Value::Array(vec![1.into(), Value::Null, 2.into()]);
+        assert_eq!(a.array_element_kind(), Ok(ValueKind::Number));
+        assert_eq!(
+            Value::Array(vec![]).array_element_kind(),
+            Ok(ValueKind::Unknown)
+        );
+
+        let mixed = Value::Array(vec![1.into(), "two".into()]);
+        assert!(matches!(
+            mixed.array_element_kind(),
+            Err(Error::TypeMismatch(_))
+        ));
+        assert!(Value::from(1).array_element_kind().is_err());
+    }
+
+    #[test]
+    fn value_estimated_sql_len() {
+        let values = vec![
+            Value::from(true),
+            Value::from(-12),
+            Value::Number("0.05".parse().unwrap()),
+            Value::from("it's"),
+            Value::TypedString("date".into(), "2024-01-02".into()),
+            Value::TypedString("timestamp".into(), "it''s".into()),
+            Value::Array(vec![1.into(), "a".into()]),
+            Value::Dict(vec![("k".into(), 1.into())]),
+            Value::typed_null("int").unwrap(),
+            Value::Bytes(vec![0xab, 0x01]),
+        ];
+        for v in values {
+            let rendered = v.to_sql_literal().unwrap();
+            assert_eq!(v.estimated_sql_len(), rendered.len(), "{}", rendered);
+        }
+    }
+
+    #[test]
+    fn value_try_into_primitives() {
+        assert_eq!(i64::try_from(Value::from(42)), Ok(42));
+        assert_eq!(i32::try_from(Value::from(-7)), Ok(-7));
+        assert_eq!(u64::try_from(Value::from(7u64)), Ok(7));
+        assert_eq!(f64::try_from(Value::from(1.5)), Ok(1.5));
+        assert_eq!(bool::try_from(Value::from(true)), Ok(true));
+        assert_eq!(String::try_from(Value::from("s")), Ok("s".to_string()));
+
+        let big = Value::from(i64::MAX);
+        assert!(matches!(i32::try_from(big), Err(Error::TypeMismatch(_))));
+        assert!(u64::try_from(Value::from(-1)).is_err());
+        assert!(i64::try_from(Value::from(1.5)).is_err());
+        assert!(i64::try_from(Value::from("1")).is_err());
+        assert!(bool::try_from(Value::Null).is_err());
+    }
+
+    #[test]
+    fn value_accessors() {
+        assert_eq!(Value::from(true).as_bool(), Some(true));
+        assert_eq!(Value::from(1).as_bool(), None);
+        assert_eq!(Value::from("s").as_str(), Some("s"));
+        assert_eq!(Value::TypedString("date".into(), "s".into()).as_str(), None);
+        assert_eq!(Value::from(7).as_number(), Some(&Number::from(7)));
+        assert_eq!(Value::from("7").as_number(), None);
+        let array = Value::Array(vec![1.into()]);
+        assert_eq!(array.as_array(), Some(&[Value::from(1)][..]));
+        assert_eq!(Value::Null.as_array(), None);
+        assert!(Value::Null.is_null());
+        assert!(Value::typed_null("int").unwrap().is_null());
+        assert!(!Value::from("").is_null());
+    }
+
+    #[test]
+    fn number_equality_ignores_scale() {
+        let one: Value = Value::Number("1.0".parse().unwrap());
+        assert_eq!(one, Value::from(1));
+        assert_eq!(one.to_string(), "1.0");
+        assert_eq!(
+            Value::Array(vec![one.clone()]),
+            Value::Array(vec![Value::from(1)])
+        );
+
+        let keys: std::collections::HashSet<Value> = [one.clone(), 1.into()].into();
+        assert_eq!(keys.len(), 1);
+
+        let ps = ParameterSet::from(vec![one, 1.into(), "1".into()]);
+        let (deduped, remap) = ps.dedup();
+        assert_eq!(deduped.values.len(), 3);
+        assert_eq!(remap, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn value_ordering() {
+        let mut values = vec![
+            Value::Null,
+            Value::Dict(vec![("b".into(), 1.into())]),
+            "b".into(),
+            Value::Array(vec![1.into(), 2.into()]),
+            Value::Number("10.50".parse().unwrap()),
+            Value::Dict(vec![("a".into(), 2.into())]),
+            true.into(),
+            "a".into(),
+            2.into(),
+            Value::Array(vec![1.into()]),
+            false.into(),
+        ];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                false.into(),
+                true.into(),
+                2.into(),
+                Value::Number("10.5".parse().unwrap()),
+                "a".into(),
+                "b".into(),
+                Value::Array(vec![1.into()]),
+                Value::Array(vec![1.into(), 2.into()]),
+                Value::Dict(vec![("a".into(), 2.into())]),
+                Value::Dict(vec![("b".into(), 1.into())]),
+                Value::Null,
+            ]
+        );
+        assert!(Value::from(9) < Value::from(10));
+    }
+
+    #[test]
+    fn value_display() {
+        assert_eq!(Value::from("it's").to_string(), "'it''s'");
+        assert_eq!(
+            Value::from("'; drop table t; --").to_string(),
+            "'''; drop table t; --'"
+        );
+        assert_eq!(Value::from("a''b").to_string(), "'a''''b'");
+        assert_eq!(Value::from(true).to_string(), "TRUE");
+        assert_eq!(Value::from(false).to_string(), "FALSE");
+        assert_eq!(Value::Null.to_string(), "NULL");
+        assert_eq!(Value::from(-1.5).to_string(), "-1.5");
+        assert_eq!(
+            Value::Array(vec![1.into(), "x".into(), Value::Null]).to_string(),
+            "ARRAY[1, 'x', NULL]"
+        );
+
+        // Outside of quoting and boolean case, the text matches what gets
+        // inlined into statements.
+        let values = vec![
+            Value::from("it's"),
+            Value::TypedString("date".into(), "2024-01-02".into()),
+            Value::Dict(vec![("k".into(), Value::Array(vec![1.into()]))]),
+            Value::typed_null("int").unwrap(),
+            Value::Bytes(vec![0xab, 0x01]),
+        ];
+        for v in values {
+            assert_eq!(v.to_string(), v.to_sql_literal().unwrap());
+        }
+    }
+
+    #[test]
+    fn number_conversions() {
+        let n: Number = "-12345678901234567890.000100".parse().unwrap();
+        assert_eq!(n.to_string(), "-12345678901234567890.000100");
+        assert!("1.2.3".parse::<Number>().is_err());
+
+        assert_eq!(i64::try_from(Number::from(42u8)), Ok(42));
+        assert!(i32::try_from(Number::from(i64::MAX)).is_err());
+        assert!(i64::try_from(Number::try_from(1.5).unwrap()).is_err());
+        assert_eq!(f64::try_from(Number::try_from(0.25f32).unwrap()), Ok(0.25));
+        assert!(Number::try_from(f64::NAN).is_err());
+
+        assert_eq!(Value::from(n.clone()), Value::Number(n));
+    }
+
+    #[test]
+    fn value_flatten_arrays() {
+        let nested = Value::Array(vec![
+            Value::Array(vec![1.into(), 2.into()]),
+            3.into(),
+            Value::Array(vec![Value::Array(vec![4.into()])]),
+        ]);
+        assert_eq!(
+            nested.flatten_arrays(),
+            Value::Array(vec![
+                1.into(),
+                2.into(),
+                3.into(),
+                Value::Array(vec![4.into()])
+            ])
+        );
+        assert_eq!(Value::from(1).flatten_arrays(), Value::from(1));
+    }
+
+    #[test]
+    fn value_like_literal() {
+        assert_eq!(
+            Value::like_literal(r"50%_off\now"),
+            Value::from(r"50\%\_off\\now")
+        );
+        assert_eq!(Value::like_literal("plain"), Value::from("plain"));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn resolving_chrono_values() {
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        let sql = "select $1, $2, $3, $4";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let naive = date.and_hms_micro_opt(3, 4, 5, 120_000).unwrap();
+        let utc = Utc.from_utc_datetime(&date.and_hms_opt(3, 4, 5).unwrap());
+        let ps = ParameterSet::from(vec![
+            date.into(),
+            naive.into(),
+            utc.into(),
+            naive.and_utc().into(),
+        ]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT DATE '2024-01-02', TIMESTAMP '2024-01-02 03:04:05.120', \
+TIMESTAMP WITH TIME ZONE '2024-01-02 03:04:05+00:00', \
+TIMESTAMP WITH TIME ZONE '2024-01-02 03:04:05.120+00:00'"
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn resolving_uuid_values() {
+        let sql = "select * from t where id = $1";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let id = uuid::Uuid::parse_str("67E55044-10B1-426F-9247-BB680E5FE0C8").unwrap();
+        let ps = ParameterSet::from(vec![id.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM t WHERE id = UUID '67e55044-10b1-426f-9247-bb680e5fe0c8'"
+        );
+
+        assert_eq!(uuid::Uuid::try_from(Value::from(id)).unwrap(), id);
+        assert!(uuid::Uuid::try_from(Value::from("not-a-uuid")).is_err());
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn decoding_postgres_columns() {
+        use super::postgres::decode;
+        use tokio_postgres::types::Type;
+
+        let numeric = |weight: i16, sign: u16, scale: u16, digits: &[i16]| {
+            let mut raw = vec![];
+            for w in [digits.len() as i16, weight, sign as i16, scale as i16] {
+                raw.extend(w.to_be_bytes());
+            }
+            for d in digits {
+                raw.extend(d.to_be_bytes());
+            }
+            raw
+        };
+        let cases: Vec<(Type, Vec<u8>, &str)> = vec![
+            (Type::BOOL, vec![1], "true"),
+            (Type::INT2, (-2i16).to_be_bytes().to_vec(), "-2"),
+            (Type::INT4, 256i32.to_be_bytes().to_vec(), "256"),
+            (Type::INT8, 42i64.to_be_bytes().to_vec(), "42"),
+            (Type::FLOAT4, 0.25f32.to_be_bytes().to_vec(), "0.25"),
+            (Type::FLOAT8, 1.5f64.to_be_bytes().to_vec(), "1.5"),
+            (Type::TEXT, b"it's".to_vec(), "'it''s'"),
+            (Type::BYTEA, vec![0, 0xff], "X'00FF'"),
+            (Type::NUMERIC, numeric(0, 0, 2, &[12, 5000]), "12.50"),
+            (Type::NUMERIC, numeric(-1, 0x4000, 3, &[10]), "-0.001"),
+            (Type::NUMERIC, numeric(1, 0, 0, &[1]), "10000"),
+            (Type::NUMERIC, numeric(0, 0, 0, &[]), "0"),
+        ];
+        for (typ, raw, sql) in cases {
+            let v = decode(&typ, Some(&raw)).unwrap();
+            assert_eq!(v.to_sql_literal().unwrap(), sql, "{}", typ);
+        }
+
+        assert_eq!(
+            decode(&Type::BYTEA, Some(&[1])).unwrap(),
+            Value::Bytes(vec![1])
+        );
+        assert_eq!(decode(&Type::NUMERIC, None).unwrap(), Value::Null);
+        let nan = numeric(0, 0xC000, 0, &[]);
+        assert!(matches!(
+            decode(&Type::NUMERIC, Some(&nan)),
+            Err(Error::Conversion(_))
+        ));
+        for raw in [f64::NAN.to_be_bytes(), f64::INFINITY.to_be_bytes()] {
+            assert!(matches!(
+                decode(&Type::FLOAT8, Some(&raw)),
+                Err(Error::Conversion(_))
+            ));
+        }
+        assert!(matches!(
+            decode(&Type::FLOAT4, Some(&f32::NAN.to_be_bytes())),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            decode(&Type::JSON, Some(b"{}")),
+            Err(Error::Conversion(_))
+        ));
+    }
+
+    #[test]
+    fn resolving_duration_interval() {
+        let sql = "select now() - $1, now() - $2";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![
+            Duration::from_secs(3600).into(),
+            Duration::from_millis(1500).into(),
+        ]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT now() - INTERVAL '3600 seconds', now() - INTERVAL '1.5 seconds'"
+        );
+    }
+
+    #[test]
+    fn resolving_json_value_as_map() {
+        let json = serde_json::json!({"id": u64::MAX, "tags": ["a", "b"], "ok": null});
+        let dialect = DuckDbDialect {};
+        let mut rs = Parser::parse_sql(&dialect, "select $1").unwrap();
+
+        let ps = ParameterSet::from(vec![json.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT MAP {'id': 18446744073709551615, 'ok': NULL, 'tags': ARRAY['a', 'b']}"
+        );
+    }
+
+    #[test]
+    fn value_from_json_map() {
+        let json = serde_json::json!({"a": 1, "b": {"c": [true, null, "x"]}});
+        let serde_json::Value::Object(map) = json else {
+            unreachable!()
+        };
+        assert_eq!(
+            Value::from(&map),
+            Value::Dict(vec![
+                ("a".into(), 1.into()),
+                (
+                    "b".into(),
+                    Value::Dict(vec![(
+                        "c".into(),
+                        Value::Array(vec![true.into(), Value::Null, "x".into()])
+                    )])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn resolving_function_filter() {
+        let sql = "select coalesce($1, 0), sum(x) filter (where y > $2) from t";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![Value::Null, 5.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT coalesce(NULL, 0), sum(x) FILTER (WHERE y > 5) FROM t"
+        );
+    }
+
+    #[test]
+    fn resolving_distinct_function_args() {
+        let sql = "select count(distinct $1), sum(all x + $2) from t";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["a".into(), 1.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT count(DISTINCT 'a'), sum(ALL x + 1) FROM t"
+        );
+    }
+
+    #[test]
+    fn resolving_substring_and_similar_to() {
+        let sql = "select substring(col from $1 for $2) from t where col similar to $3";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![2.into(), 3.into(), "%(b|d)%".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT SUBSTRING(col FROM 2 FOR 3) FROM t WHERE col SIMILAR TO '%(b|d)%'"
+        );
+    }
+
+    #[test]
+    fn resolving_string_functions() {
+        let sql = "select substring($1 from $2 for $3), trim(both $4 from col), \
+overlay(col placing $5 from $6 for $7) from t";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![
+            "abcdef".into(),
+            2.into(),
+            3.into(),
+            "x".into(),
+            "yy".into(),
+            1.into(),
+            2.into(),
+        ]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT SUBSTRING('abcdef' FROM 2 FOR 3), TRIM(BOTH 'x' FROM col), \
+OVERLAY(col PLACING 'yy' FROM 1 FOR 2) FROM t"
+        );
+    }
+
+    #[test]
+    fn resolving_extract_and_at_time_zone() {
+        let sql = "select extract(year from $1), $2 at time zone $3";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ts = Value::TypedString("timestamp".into(), "2024-01-02 03:04:05".into());
+        let ps = ParameterSet::from(vec![ts.clone(), ts, "UTC".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT EXTRACT(YEAR FROM TIMESTAMP '2024-01-02 03:04:05'), \
+TIMESTAMP '2024-01-02 03:04:05' AT TIME ZONE 'UTC'"
+        );
+    }
+
+    #[test]
+    fn resolving_time_bucket_expression() {
+        let sql = "select date_trunc('day', now() - $1::interval) from t";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["7 days".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT date_trunc('day', now() - '7 days'::INTERVAL) FROM t"
+        );
+    }
+
+    #[test]
+    fn substituting_placeholder_with_expr() {
+        let sql = "select $1, $2 from t where a > $1";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let column = Expr::CompoundIdentifier(vec![Ident::new("t"), Ident::new("b")]);
+        substitute_placeholder(&mut rs[0], "$1", &column);
+        assert_eq!(rs[0].to_string(), "SELECT t.b, $2 FROM t WHERE a > t.b");
+    }
+
+    #[test]
+    fn resolving_is_predicates() {
+        let sql = "select $1 is true, $2 is not unknown, a is distinct from $3 from t";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![true.into(), Value::Null, 3.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT true IS TRUE, NULL IS NOT UNKNOWN, a IS DISTINCT FROM 3 FROM t"
+        );
+    }
+
+    #[test]
+    fn resolving_with_render_limits() {
+        let sql = "select $1, $2";
+        let dialect = GenericDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["short".into(), "x".repeat(100).into()]);
+        let limits = RenderLimits {
+            max_value_size: Some(64),
+            max_statement_size: None,
+        };
+        let r = resolve_all_limited(&ps, &mut rs.clone(), &limits);
+        assert!(matches!(r, Err(Error::TooLarge(_))));
+
+        let ps = ParameterSet::from(vec!["short".into(), "x".repeat(60).into()]);
+        resolve_all_limited(&ps, &mut rs.clone(), &limits).unwrap();
+
+        let limits = RenderLimits {
+            max_value_size: None,
+            max_statement_size: Some(32),
+        };
+        let mut untouched = rs.clone();
+        let r = resolve_all_limited(&ps, &mut untouched, &limits);
+        assert!(matches!(r, Err(Error::TooLarge(_))));
+        assert_eq!(untouched, rs);
+    }
+
+    #[test]
+    fn resolving_create_table_checks() {
+        let sql = "create table t (x int default $1, check (x > $2), \
+y int generated always as (x + $3) stored)";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![1.into(), 0.into(), 10.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "CREATE TABLE t (x INT DEFAULT 1, \
+y INT GENERATED ALWAYS AS (x + 10) STORED, CHECK (x > 0))"
+        );
+    }
+
+    #[test]
+    fn resolving_alter_column_default() {
+        let sql = "alter table t alter column c set default $1";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["pending".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "ALTER TABLE t ALTER COLUMN c SET DEFAULT 'pending'"
+        );
+    }
+
+    #[test]
+    fn resolving_create_view_query() {
+        let sql = "create view v as select $1 as c";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["fixed".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(rs[0].to_string(), "CREATE VIEW v AS SELECT 'fixed' AS c");
+    }
+
+    #[test]
+    fn resolving_create_view_options() {
+        let sql = "create view v with (security_barrier = $1) as select * from t where a = $2";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![true.into(), 1.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "CREATE VIEW v WITH (security_barrier = true) AS SELECT * FROM t WHERE a = 1"
+        );
+    }
+
+    #[test]
+    fn resolving_alter_table_checks() {
+        let sql = "alter table t add constraint chk check (length(x) > $1), \
+add column y int default $2";
+        let dialect = PostgreSqlDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![3.into(), 0.into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "ALTER TABLE t ADD CONSTRAINT chk CHECK (length(x) > 3), ADD COLUMN y INT DEFAULT 0"
+        );
+    }
+
+    #[test]
+    fn resolving_pivot_values() {
+        let sql = "select * from sales pivot(sum(amount * $1) for month in ($2, $3)) as p \
+unpivot(v for k in (a, b))";
+        let dialect = SnowflakeDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![2.into(), "JAN".into(), "FEB".into()]);
+
+        resolve_all_strict(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT * FROM sales PIVOT(sum(amount * 2) FOR month IN ('JAN', 'FEB')) AS p \
+UNPIVOT(v FOR k IN (a, b))"
+        );
+    }
+
+    #[test]
+    fn resolving_case_in_order_by() {
+        let sql =
+            "select row_number() over (partition by g order by case k when $1 then 0 else 1 end) \
+from t order by case x when $2 then 0 else 1 end, y";
+        let dialect = GenericDialect {};
+        let mut rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec!["a".into(), "b".into()]);
+
+        resolve_all(&ps, &mut rs).unwrap();
+        assert_eq!(
+            rs[0].to_string(),
+            "SELECT row_number() OVER (PARTITION BY g ORDER BY CASE k WHEN 'a' THEN 0 ELSE 1 END) \
+FROM t ORDER BY CASE x WHEN 'b' THEN 0 ELSE 1 END, y"
+        );
+    }
+
+    #[test]
+    fn resolving_null_as_typed() {
+        let sql = "select $1, $2, $3";
+        let dialect = GenericDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from(vec![
+            Value::Null,
+            Value::typed_null("int").unwrap(),
+            Value::Array(vec![1.into(), Value::Null]),
+        ]);
+
+        let mut plain = rs.clone();
+        resolve_all_with_options(&ps, &mut plain, &RenderOptions::default()).unwrap();
+        assert_eq!(
+            plain[0].to_string(),
+            "SELECT NULL, CAST(NULL AS INT), ARRAY[1, NULL]"
+        );
+
+        let opts = RenderOptions {
+            null_as: Some("text".into()),
+            ..Default::default()
+        };
+        let mut typed = rs.clone();
+        resolve_all_with_options(&ps, &mut typed, &opts).unwrap();
+        assert_eq!(
+            typed[0].to_string(),
+            "SELECT CAST(NULL AS TEXT), CAST(NULL AS INT), ARRAY[1, CAST(NULL AS TEXT)]"
+        );
+
+        // Checked up front, even when nothing is NULL.
+        let opts = RenderOptions {
+            null_as: Some("text); drop table t; --".into()),
+            ..Default::default()
+        };
+        let ps = ParameterSet::from(vec![1.into(), 2.into(), 3.into()]);
+        let r = resolve_all_with_options(&ps, &mut rs.clone(), &opts);
+        assert!(matches!(r, Err(Error::Conversion(_))));
+    }
+
+    #[test]
+    fn rendering_annotated_bindings() {
+        let sql = "select a from t where b = $1 and c = $2";
+        let dialect = GenericDialect {};
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+
+        let ps = ParameterSet::from(vec![42.into(), "*/ drop".into()]);
+
+        let plain = render_statement(&ps, &rs[0], &RenderOptions::default()).unwrap();
+        assert_eq!(plain, "SELECT a FROM t WHERE b = 42 AND c = '*/ drop'");
+
+        let opts = RenderOptions {
+            annotate_bindings: true,
+            ..Default::default()
+        };
+        let annotated = render_statement(&ps, &rs[0], &opts).unwrap();
+        assert_eq!(
+            annotated,
+            "SELECT a FROM t WHERE b = 42 AND c = '*/ drop' /* $1 = 42 */ /* $2 = '* / drop' */"
+        );
+        Parser::parse_sql(&dialect, &annotated).unwrap();
+
+        let sql = "select $10, $2, $1, $3, $4, $5, $6, $7, $8, $9";
+        let rs = Parser::parse_sql(&dialect, sql).unwrap();
+        let ps = ParameterSet::from((1..=10).map(Value::from).collect::<Vec<_>>());
+        let annotated = render_statement(&ps, &rs[0], &opts).unwrap();
+        let order: Vec<&str> = annotated
+            .split(" /* ")
+            .skip(1)
+            .map(|c| c.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(
+            order,
+            vec!["$1", "$2", "$3", "$4", "$5", "$6", "$7", "$8", "$9", "$10"]
+        );
+    }
 }
@@ -0,0 +1,74 @@
+use {
+    super::{resolve_all, result::Error, value::Value, Parameters, Rv, R},
+    sqlparser::ast::Statement,
+};
+
+/// Size bounds, in bytes of rendered SQL, enforced while resolving.
+/// `None` means unbounded.
+#[derive(Debug, Clone, Default)]
+pub struct RenderLimits {
+    pub max_value_size: Option<usize>,
+    pub max_statement_size: Option<usize>,
+}
+
+pub(crate) struct LimitedParameters<'a> {
+    pub(crate) inner: &'a dyn Parameters,
+    pub(crate) limits: &'a RenderLimits,
+}
+
+impl<'a> LimitedParameters<'a> {
+    fn check(&self, p: &str, v: Value) -> Rv {
+        if let Some(max) = self.limits.max_value_size {
+            let size = v.to_sql_literal()?.len();
+            if size > max {
+                return Err(Error::TooLarge(format!(
+                    "{} renders to {} bytes, limit is {}",
+                    p, size, max
+                )));
+            }
+        }
+        Ok(v)
+    }
+}
+
+impl<'a> Parameters for LimitedParameters<'a> {
+    fn get(&self, i: usize) -> Rv {
+        let v = self.inner.get(i)?;
+        self.check(&format!("${}", i), v)
+    }
+
+    fn get_named(&self, name: &str) -> Rv {
+        let v = self.inner.get_named(name)?;
+        self.check(&format!(":{}", name), v)
+    }
+}
+
+/// Fails if any resolved statement renders larger than the limit.
+pub(crate) fn check_statement_sizes(s: &[Statement], limits: &RenderLimits) -> R {
+    if let Some(max) = limits.max_statement_size {
+        for stmt in s.iter() {
+            let size = stmt.to_string().len();
+            if size > max {
+                return Err(Error::TooLarge(format!(
+                    "statement renders to {} bytes, limit is {}",
+                    size, max
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Like `resolve_all`, but rejects bound values, and resolved statements,
+/// whose rendered SQL exceeds `limits`. The statements are only changed
+/// when everything fits.
+pub fn resolve_all_limited(ps: &dyn Parameters, s: &mut [Statement], limits: &RenderLimits) -> R {
+    let limited = LimitedParameters { inner: ps, limits };
+    let mut resolved = s.to_vec();
+    resolve_all(&limited, &mut resolved)?;
+    check_statement_sizes(&resolved, limits)?;
+    for (stmt, r) in s.iter_mut().zip(resolved) {
+        *stmt = r;
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use {
+    super::{placeholder_name, result::Error, ParameterSet, Parameters, Rv, Value},
+    sqlparser::ast::{visit_expressions_mut, Expr, Statement, Value as AstValue},
+    std::{collections::BTreeMap, ops::ControlFlow},
+};
+
+/// Values bound by name, for `:name` and `@name` placeholders. One binding serves
+/// every occurrence of its name.
+#[derive(Default)]
+pub struct NamedParameterSet {
+    pub values: BTreeMap<String, Value>,
+}
+
+impl NamedParameterSet {
+    pub fn add_named(&mut self, name: &str, value: Value) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    /// Like `add_named`, for chaining.
+    pub fn bind(mut self, name: &str, value: Value) -> Self {
+        self.add_named(name, value);
+        self
+    }
+
+    /// Rewrites named placeholders to `$1`, `$2`, ... in order of first
+    /// appearance, and returns the values in that order, for drivers that
+    /// only bind positionally. Repeated names share one position.
+    pub fn to_positional(&self, s: &mut Vec<Statement>) -> Result<ParameterSet, Error> {
+        let mut positions: Vec<String> = Vec::new();
+        let mut ps = ParameterSet::default();
+        let result = visit_expressions_mut(s, |x| {
+            let Expr::Value(AstValue::Placeholder(p)) = x else {
+                return ControlFlow::Continue(());
+            };
+            let Some(name) = placeholder_name(p) else {
+                return ControlFlow::Continue(());
+            };
+            let pos = match positions.iter().position(|n| n == name) {
+                Some(pos) => pos,
+                None => match self.get_named(name) {
+                    Ok(v) => {
+                        positions.push(name.to_string());
+                        ps.add(v)
+                    }
+                    Err(e) => return ControlFlow::Break(e),
+                },
+            };
+            *p = format!("${}", pos + 1);
+            ControlFlow::Continue(())
+        });
+        if let ControlFlow::Break(e) = result {
+            return Err(e);
+        }
+        Ok(ps)
+    }
+}
+
+impl Parameters for NamedParameterSet {
+    fn get(&self, i: usize) -> Rv {
+        Err(Error::Notfound(format!("${}", i)))
+    }
+
+    fn get_named(&self, name: &str) -> Rv {
+        match self.values.get(name) {
+            Some(v) => Ok(v.clone()),
+            None => Err(Error::Notfound(format!(":{}", name))),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use super::result::Error;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// An arbitrary-precision decimal, kept opaque so the decimal crate behind
+/// it is not part of this crate's API. `from_str` and `to_string` round-trip
+/// exactly, including the scale.
+///
+/// Equality, ordering and hashing go by numeric value and ignore the scale,
+/// so `1.0 == 1` and the two are one key in a `HashMap` or `BTreeSet`. The
+/// scale still shows when the number is rendered, and `ParameterSet::dedup`
+/// keeps such values apart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(transparent)]
+pub struct Number(pub(crate) BigDecimal);
+
+impl Number {
+    pub fn is_integer(&self) -> bool {
+        self.0.is_integer()
+    }
+}
+
+impl FromStr for Number {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        s.parse::<BigDecimal>()
+            .map(Number)
+            .map_err(|e| Error::Conversion(format!("invalid number {:?}: {}", s, e)))
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t: ty),*) => {
+        $(
+            impl From<$t> for Number {
+                fn from(i: $t) -> Self {
+                    Number(BigDecimal::from(i))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl From<isize> for Number {
+    fn from(i: isize) -> Self {
+        Number(BigDecimal::from(i64::try_from(i).unwrap_or(0i64)))
+    }
+}
+
+impl From<usize> for Number {
+    fn from(i: usize) -> Self {
+        Number(BigDecimal::from(u64::try_from(i).unwrap_or(0u64)))
+    }
+}
+
+macro_rules! impl_try_from_float {
+    ($($t: ty),*) => {
+        $(
+            /// Fails for NaN and infinities.
+            impl TryFrom<$t> for Number {
+                type Error = Error;
+
+                fn try_from(f: $t) -> Result<Self, Error> {
+                    BigDecimal::try_from(f).map(Number).map_err(|_| {
+                        Error::Conversion(format!("{} is not a finite number", f))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_float!(f32, f64);
+
+macro_rules! impl_try_into_int {
+    ($t: ty) => {
+        impl TryFrom<Number> for $t {
+            type Error = Error;
+
+            fn try_from(n: Number) -> Result<Self, Error> {
+                if !n.is_integer() {
+                    return Err(Error::TypeMismatch(format!("{} is not an integer", n)));
+                }
+                let i = n.0.to_i128().and_then(|i| <$t>::try_from(i).ok());
+                i.ok_or_else(|| {
+                    Error::TypeMismatch(format!("{} out of range for {}", n, stringify!($t)))
+                })
+            }
+        }
+    };
+}
+
+impl_try_into_int!(i32);
+impl_try_into_int!(i64);
+impl_try_into_int!(u64);
+
+impl TryFrom<Number> for f64 {
+    type Error = Error;
+
+    fn try_from(n: Number) -> Result<Self, Error> {
+        n.0.to_f64()
+            .filter(|f| f.is_finite())
+            .ok_or_else(|| Error::TypeMismatch(format!("{} out of range for f64", n)))
+    }
+}
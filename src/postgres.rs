@@ -0,0 +1,135 @@
+use {
+    super::{number::Number, result::Error, value::Value, Parameters, Rv},
+    bigdecimal::{num_bigint::BigInt, BigDecimal},
+    std::error::Error as StdError,
+    tokio_postgres::{
+        types::{FromSql, Type},
+        Row,
+    },
+};
+
+/// Binds `$N` to column `N - 1` of a `tokio_postgres::Row`.
+/// `BYTEA` columns bind back as bytea only with
+/// `RenderOptions::bytes_as_bytea`, which `Resolver` sets for Postgres.
+pub struct RowParameters<'a> {
+    pub row: &'a Row,
+}
+
+impl<'a> RowParameters<'a> {
+    pub fn new(row: &'a Row) -> Self {
+        RowParameters { row }
+    }
+}
+
+/// A column's value in Postgres' binary format, decoded by `decode`.
+struct Raw<'a>(&'a [u8]);
+
+impl<'a> FromSql<'a> for Raw<'a> {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn StdError + Sync + Send>> {
+        Ok(Raw(raw))
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+/// `NUMERIC`: base-10000 digits, the weight of the first one, a sign and
+/// the display scale.
+struct Numeric(Number);
+
+impl<'a> FromSql<'a> for Numeric {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn StdError + Sync + Send>> {
+        let word = |i: usize| -> Result<i16, Box<dyn StdError + Sync + Send>> {
+            let bytes = raw.get(i * 2..i * 2 + 2).ok_or("truncated numeric")?;
+            Ok(i16::from_be_bytes([bytes[0], bytes[1]]))
+        };
+        let ndigits = word(0)?.max(0) as usize;
+        let weight = i64::from(word(1)?);
+        let sign = word(2)? as u16;
+        let scale = i64::from(word(3)? as u16);
+        let mut n = BigInt::from(0);
+        for i in 0..ndigits {
+            n = n * 10000 + word(4 + i)?;
+        }
+        let d = BigDecimal::new(n, 4 * (ndigits as i64 - 1 - weight)).with_scale(scale);
+        match sign {
+            0x0000 => Ok(Numeric(Number(d))),
+            0x4000 => Ok(Numeric(Number(-d))),
+            _ => Err("numeric is NaN or infinite".into()),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+}
+
+impl From<Numeric> for Value {
+    fn from(n: Numeric) -> Self {
+        Value::Number(n.0)
+    }
+}
+
+fn from_sql<'a, T>(typ: &Type, raw: &'a [u8]) -> Rv
+where
+    T: FromSql<'a> + Into<Value>,
+{
+    T::from_sql(typ, raw)
+        .map(Into::into)
+        .map_err(|e| Error::Conversion(format!("{}", &e)))
+}
+
+/// Floats become numbers; NaN and infinities have no number to become.
+fn from_float<'a, T>(typ: &Type, raw: &'a [u8]) -> Rv
+where
+    T: FromSql<'a>,
+    Number: TryFrom<T, Error = Error>,
+{
+    let f = T::from_sql(typ, raw).map_err(|e| Error::Conversion(format!("{}", &e)))?;
+    Number::try_from(f).map(Value::Number)
+}
+
+/// Decodes a column value of type `typ`; `None` is SQL NULL.
+pub(crate) fn decode(typ: &Type, raw: Option<&[u8]>) -> Rv {
+    let Some(raw) = raw else {
+        return Ok(Value::Null);
+    };
+    match *typ {
+        Type::BOOL => from_sql::<bool>(typ, raw),
+        Type::CHAR => from_sql::<i8>(typ, raw),
+        Type::INT2 => from_sql::<i16>(typ, raw),
+        Type::INT4 => from_sql::<i32>(typ, raw),
+        Type::INT8 => from_sql::<i64>(typ, raw),
+        Type::OID => from_sql::<u32>(typ, raw),
+        Type::FLOAT4 => from_float::<f32>(typ, raw),
+        Type::FLOAT8 => from_float::<f64>(typ, raw),
+        Type::NUMERIC => from_sql::<Numeric>(typ, raw),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
+            from_sql::<String>(typ, raw)
+        }
+        Type::BYTEA => from_sql::<&[u8]>(typ, raw),
+        _ => Err(Error::Conversion(format!(
+            "unsupported column type {}",
+            typ
+        ))),
+    }
+}
+
+impl<'a> Parameters for RowParameters<'a> {
+    fn get(&self, i: usize) -> Rv {
+        if i == 0 || i > self.row.len() {
+            return Err(Error::Notfound(format!("${}", i)));
+        }
+        let idx = i - 1;
+        let typ = self.row.columns()[idx].type_();
+        let raw = self
+            .row
+            .try_get::<_, Option<Raw>>(idx)
+            .map_err(|e| Error::Conversion(format!("{}", &e)))?;
+        decode(typ, raw.map(|r| r.0)).map_err(|e| match e {
+            Error::Conversion(m) => Error::Conversion(format!("{} for ${}", m, i)),
+            e => e,
+        })
+    }
+}
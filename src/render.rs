@@ -0,0 +1,112 @@
+use {
+    super::{
+        placeholder_name, placeholder_names, placeholder_to_usize, resolve, resolve_all,
+        resolve_cloned,
+        result::Error,
+        value::{parse_data_type, Value},
+        Parameters, Rv, R,
+    },
+    sqlparser::ast::Statement,
+};
+
+/// Options for `render_statement` and `resolve_all_with_options`. All off
+/// by default.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Append a `/* $1 = 42 */` comment for each inlined placeholder.
+    pub annotate_bindings: bool,
+    /// Render every bound NULL as `CAST(NULL AS <type>)` with this type.
+    pub null_as: Option<String>,
+    /// Render bytes as `BYTEA '\x..'` rather than `X'..'`, which Postgres
+    /// reads as a bit string. `Resolver` turns this on for Postgres.
+    pub bytes_as_bytea: bool,
+}
+
+pub(crate) struct OptionParameters<'a> {
+    inner: &'a dyn Parameters,
+    opts: &'a RenderOptions,
+}
+
+impl<'a> OptionParameters<'a> {
+    /// Fails if `null_as` is not a type name, before anything is resolved.
+    pub(crate) fn new(inner: &'a dyn Parameters, opts: &'a RenderOptions) -> Result<Self, Error> {
+        if let Some(typ) = &opts.null_as {
+            parse_data_type(typ)?;
+        }
+        Ok(OptionParameters { inner, opts })
+    }
+
+    fn apply(&self, v: Value) -> Value {
+        match (v, &self.opts.null_as) {
+            (Value::Null, Some(typ)) => Value::TypedNull(typ.clone()),
+            (Value::Bytes(bytes), _) if self.opts.bytes_as_bytea => {
+                let digits: String = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+                Value::TypedString("bytea".into(), format!("\\x{}", digits))
+            }
+            (Value::Array(elems), _) if self.opts.null_as.is_some() || self.opts.bytes_as_bytea => {
+                Value::Array(elems.into_iter().map(|x| self.apply(x)).collect())
+            }
+            (v, _) => v,
+        }
+    }
+}
+
+impl<'a> Parameters for OptionParameters<'a> {
+    fn get(&self, i: usize) -> Rv {
+        self.inner.get(i).map(|v| self.apply(v))
+    }
+
+    fn get_named(&self, name: &str) -> Rv {
+        self.inner.get_named(name).map(|v| self.apply(v))
+    }
+}
+
+/// Like `resolve_all`, applying the value policies in `opts`.
+pub fn resolve_all_with_options(
+    ps: &dyn Parameters,
+    s: &mut [Statement],
+    opts: &RenderOptions,
+) -> R {
+    resolve_all(&OptionParameters::new(ps, opts)?, s)
+}
+
+/// Keeps a value from closing, or opening a nested, block comment.
+fn escape_comment(s: &str) -> String {
+    s.replace("*/", "* /").replace("/*", "/ *")
+}
+
+/// Resolves a copy of the statement and renders it as SQL text.
+pub fn render_statement(
+    ps: &dyn Parameters,
+    s: &Statement,
+    opts: &RenderOptions,
+) -> Result<String, Error> {
+    let ps = &OptionParameters::new(ps, opts)?;
+    let resolved = resolve_cloned(ps, s)?;
+    let mut sql = resolved.to_string();
+    if opts.annotate_bindings {
+        push_annotations(&mut sql, ps, s, &resolved)?;
+    }
+    Ok(sql)
+}
+
+/// Appends a `/* p = value */` comment for each placeholder of `original`
+/// that is gone from `resolved`, numbered ones by position and then named
+/// ones by name.
+pub(crate) fn push_annotations(
+    sql: &mut String,
+    ps: &dyn Parameters,
+    original: &Statement,
+    resolved: &Statement,
+) -> R {
+    let names = placeholder_names(original);
+    let left = placeholder_names(resolved);
+    // Anonymous placeholders have no single value to show.
+    let mut gone: Vec<&String> = names.difference(&left).filter(|p| *p != "?").collect();
+    gone.sort_by_key(|p| (placeholder_name(p).is_some(), placeholder_to_usize(p)));
+    for p in gone {
+        let v = resolve(ps, p)?.to_sql_literal()?;
+        sql.push_str(&format!(" /* {} = {} */", p, escape_comment(&v)));
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use {
+    super::{
+        check::resolve_all_checked,
+        check_resolved,
+        limits::{check_statement_sizes, LimitedParameters, RenderLimits},
+        render::{push_annotations, OptionParameters, RenderOptions},
+        resolve_all,
+        result::Error,
+        Parameters,
+    },
+    sqlparser::{
+        ast::Statement,
+        dialect::{Dialect, PostgreSqlDialect},
+        parser::Parser,
+    },
+};
+
+/// Parses, resolves and renders SQL in one call, with the modes of the
+/// `resolve_all_*` functions combined as configured. Everything is off by
+/// default, which behaves like `resolve_all`.
+pub struct Resolver<'d> {
+    dialect: &'d dyn Dialect,
+    strict: bool,
+    checked: bool,
+    max_depth: Option<usize>,
+    limits: RenderLimits,
+    options: RenderOptions,
+}
+
+impl<'d> Resolver<'d> {
+    pub fn new(dialect: &'d dyn Dialect) -> Self {
+        Resolver {
+            dialect,
+            strict: false,
+            checked: false,
+            max_depth: None,
+            limits: RenderLimits::default(),
+            options: RenderOptions {
+                bytes_as_bytea: dialect.is::<PostgreSqlDialect>(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Fail if a placeholder is left unresolved, as `resolve_all_strict`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Check bound values against their context first, as
+    /// `resolve_all_checked`.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// The parser's nesting limit; deeper SQL fails to parse instead of
+    /// exhausting the stack.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Size limits, as `resolve_all_limited`.
+    pub fn limits(mut self, limits: RenderLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Value policies and annotations, as `render_statement`. Bytes render
+    /// as `BYTEA` for Postgres either way.
+    pub fn options(mut self, options: RenderOptions) -> Self {
+        let bytes_as_bytea = options.bytes_as_bytea || self.dialect.is::<PostgreSqlDialect>();
+        self.options = RenderOptions {
+            bytes_as_bytea,
+            ..options
+        };
+        self
+    }
+
+    fn parse(&self, sql: &str) -> Result<Vec<Statement>, Error> {
+        let mut parser = Parser::new(self.dialect);
+        if let Some(depth) = self.max_depth {
+            parser = parser.with_recursion_limit(depth);
+        }
+        Ok(parser.try_with_sql(sql)?.parse_statements()?)
+    }
+
+    /// `ps` has the render options applied already.
+    fn resolve_parsed(&self, ps: &dyn Parameters, s: &mut [Statement]) -> Result<(), Error> {
+        let ps = &LimitedParameters {
+            inner: ps,
+            limits: &self.limits,
+        };
+        if self.checked {
+            resolve_all_checked(ps, s)?;
+        } else {
+            resolve_all(ps, s)?;
+        }
+        if self.strict {
+            check_resolved(s)?;
+        }
+        check_statement_sizes(s, &self.limits)
+    }
+
+    /// Parses and resolves `sql`.
+    pub fn resolve_statements(
+        &self,
+        sql: &str,
+        ps: &dyn Parameters,
+    ) -> Result<Vec<Statement>, Error> {
+        let ps = &OptionParameters::new(ps, &self.options)?;
+        let mut statements = self.parse(sql)?;
+        self.resolve_parsed(ps, &mut statements)?;
+        Ok(statements)
+    }
+
+    /// Parses, resolves and renders `sql`, joining statements with `; `.
+    pub fn resolve(&self, sql: &str, ps: &dyn Parameters) -> Result<String, Error> {
+        let ps = &OptionParameters::new(ps, &self.options)?;
+        let original = self.parse(sql)?;
+        let mut statements = original.clone();
+        self.resolve_parsed(ps, &mut statements)?;
+        let mut rendered = Vec::with_capacity(statements.len());
+        for (original, resolved) in original.iter().zip(statements.iter()) {
+            let mut sql = resolved.to_string();
+            if self.options.annotate_bindings {
+                push_annotations(&mut sql, ps, original, resolved)?;
+            }
+            rendered.push(sql);
+        }
+        Ok(rendered.join("; "))
+    }
+}
@@ -1,30 +1,79 @@
 use {
-    serde::Serialize,
+    serde::{Serialize, Serializer},
     serde_json::Error as JSONError,
+    sqlparser::parser::ParserError,
     std::{
         fmt,
         fmt::{Debug, Display},
     },
 };
 
+/// The `serde_json::Error` behind `Error::JSON`. Compares and serializes
+/// as its message.
+#[derive(Debug)]
+pub struct JsonError(pub JSONError);
+
+impl PartialEq for JsonError {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl Serialize for JsonError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
 #[derive(Serialize, Debug, PartialEq)]
 pub enum Error {
-    JSON(String),
+    JSON(JsonError),
     Notfound(String),
+    TypeMismatch(String),
+    Parse(String),
+    Collision(String),
+    Unresolved(String),
+    Binary(String),
+    Conversion(String),
+    TooLarge(String),
+    Positional(String),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
-            Self::JSON(v) => write!(f, "json: {}", &v),
+            Self::JSON(v) => write!(f, "json: {}", &v.0),
             Self::Notfound(v) => write!(f, "parameter {} not found.", &v),
+            Self::TypeMismatch(v) => write!(f, "type mismatch: {}", &v),
+            Self::Parse(v) => write!(f, "parse: {}", &v),
+            Self::Collision(v) => write!(f, "collision: {}", &v),
+            Self::Unresolved(v) => write!(f, "unresolved placeholders {}", &v),
+            Self::Binary(v) => write!(f, "binary: {}", &v),
+            Self::Conversion(v) => write!(f, "conversion: {}", &v),
+            Self::TooLarge(v) => write!(f, "too large: {}", &v),
+            Self::Positional(v) => write!(f, "positional: {}", &v),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::JSON(e) => Some(&e.0),
+            _ => None,
         }
     }
 }
 
 impl From<JSONError> for Error {
     fn from(e: JSONError) -> Self {
-        Self::JSON(format!("{}", &e))
+        Self::JSON(JsonError(e))
+    }
+}
+
+impl From<ParserError> for Error {
+    fn from(e: ParserError) -> Self {
+        Self::Parse(format!("{}", &e))
     }
 }
 
@@ -0,0 +1,68 @@
+use {
+    super::{
+        placeholder_names, resolve_all, result::Error, split_parameters_by_statement, Parameters,
+    },
+    sqlparser::{ast::Statement, dialect::Dialect, parser::Parser},
+    std::collections::BTreeSet,
+};
+
+/// Parsed SQL kept around to be bound many times, so the parse cost is
+/// paid once.
+pub struct Template {
+    statements: Vec<Statement>,
+    placeholder_count: usize,
+}
+
+/// Parses `sql` into a reusable `Template`.
+pub fn compile(dialect: &dyn Dialect, sql: &str) -> Result<Template, Error> {
+    let statements = Parser::parse_sql(dialect, sql)?;
+    let mut names = BTreeSet::new();
+    for stmt in statements.iter() {
+        names.extend(placeholder_names(stmt));
+    }
+    names.remove("?");
+    // Each `?` takes its own value.
+    let questions = split_parameters_by_statement(&statements)
+        .last()
+        .map_or(0, |r| r.end);
+    Ok(Template {
+        statements,
+        placeholder_count: names.len() + questions,
+    })
+}
+
+/// Parses each fragment with its own dialect, then resolves them all as
+/// one batch. A parse error names the index of the failing fragment.
+pub fn parse_and_resolve_mixed(
+    inputs: &[(Box<dyn Dialect>, &str)],
+    ps: &dyn Parameters,
+) -> Result<Vec<Statement>, Error> {
+    let mut statements = Vec::new();
+    for (i, (dialect, sql)) in inputs.iter().enumerate() {
+        match Parser::parse_sql(dialect.as_ref(), sql) {
+            Ok(mut parsed) => statements.append(&mut parsed),
+            Err(e) => return Err(Error::Parse(format!("fragment {}: {}", i, e))),
+        }
+    }
+    resolve_all(ps, &mut statements)?;
+    Ok(statements)
+}
+
+impl Template {
+    /// The number of values the statements bind: one per distinct numbered
+    /// or named placeholder, and one per `?`.
+    pub fn placeholder_count(&self) -> usize {
+        self.placeholder_count
+    }
+
+    pub fn statements(&self) -> &[Statement] {
+        &self.statements
+    }
+
+    /// Returns resolved copies of the statements.
+    pub fn bind(&self, ps: &dyn Parameters) -> Result<Vec<Statement>, Error> {
+        let mut statements = self.statements.clone();
+        resolve_all(ps, &mut statements)?;
+        Ok(statements)
+    }
+}
@@ -1,95 +1,556 @@
-use bigdecimal::BigDecimal;
+use super::{number::Number, result::Error};
+use bigdecimal::{num_bigint::Sign, RoundingMode};
 use serde::{Deserialize, Serialize};
-use sqlparser::ast::{Array, DataType, Expr, Map, MapEntry, Value as AstValue};
+use sqlparser::{
+    ast::{Array, CastKind, DataType, Expr, Ident, Map, MapEntry, TimezoneInfo, Value as AstValue},
+    dialect::GenericDialect,
+    parser::Parser,
+    tokenizer::Token,
+};
+use std::{collections::HashMap, fmt, time::Duration};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+/// Values are totally ordered: first by variant, in declaration order, then
+/// by content. Numbers compare numerically, ignoring scale (`1.0 == 1`),
+/// strings and byte strings lexicographically, and arrays element-wise. A
+/// dict compares as its list of pairs, in insertion order, key before value.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Value {
     Bool(bool),
-    Number(BigDecimal),
+    Number(Number),
     String(String),
     TypedString(String, String),
     Array(Vec<Value>),
     Dict(Vec<(Value, Value)>),
     Null,
+    TypedNull(String),
+    /// Raw binary data, inlined as a hex literal (`X'00ff'`). Encoded as
+    /// base64 in JSON.
+    #[serde(with = "base64_bytes")]
+    Bytes(Vec<u8>),
 }
 
-impl From<Value> for Expr {
-    fn from(v: Value) -> Self {
-        match v {
+mod base64_bytes {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            STANDARD.encode(bytes).serialize(s)
+        } else {
+            bytes.serialize(s)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+        if d.is_human_readable() {
+            let encoded = String::deserialize(d)?;
+            STANDARD.decode(encoded).map_err(D::Error::custom)
+        } else {
+            Vec::deserialize(d)
+        }
+    }
+}
+
+/// The kind of a non-null `Value`, as reported by `array_element_kind`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ValueKind {
+    Bool,
+    Number,
+    String,
+    TypedString(String),
+    Array,
+    Dict,
+    Bytes,
+    /// No non-null elements to go by.
+    Unknown,
+}
+
+impl Value {
+    pub(crate) fn kind(&self) -> ValueKind {
+        match self {
+            Value::Bool(_) => ValueKind::Bool,
+            Value::Number(_) => ValueKind::Number,
+            Value::String(_) => ValueKind::String,
+            Value::TypedString(typ, _) => ValueKind::TypedString(typ.clone()),
+            Value::Array(_) => ValueKind::Array,
+            Value::Dict(_) => ValueKind::Dict,
+            Value::Bytes(_) => ValueKind::Bytes,
+            Value::Null | Value::TypedNull(_) => ValueKind::Unknown,
+        }
+    }
+
+    /// The kind shared by every non-null element of an array, or `Unknown`
+    /// if there is none. Mixed kinds are an error.
+    pub fn array_element_kind(&self) -> Result<ValueKind, Error> {
+        let Value::Array(elems) = self else {
+            return Err(Error::TypeMismatch(format!("not an array: {:?}", self)));
+        };
+        let mut common = ValueKind::Unknown;
+        for elem in elems.iter() {
+            let kind = elem.kind();
+            if kind == ValueKind::Unknown {
+                continue;
+            }
+            if common == ValueKind::Unknown {
+                common = kind;
+            } else if kind != common {
+                return Err(Error::TypeMismatch(format!(
+                    "array mixes {:?} and {:?}",
+                    common, kind
+                )));
+            }
+        }
+        Ok(common)
+    }
+
+    /// A NULL rendered as `CAST(NULL AS <typ>)`, for calls that cannot
+    /// infer the type of a bare NULL. Fails unless `typ` is a single SQL
+    /// type name.
+    pub fn typed_null(typ: &str) -> Result<Value, Error> {
+        parse_data_type(typ)?;
+        Ok(Value::TypedNull(typ.to_string()))
+    }
+
+    /// A number padded or rounded (half-up) to exactly `scale` fractional
+    /// digits, e.g. `10` with scale 2 renders as `10.00`.
+    pub fn decimal_with_scale(d: Number, scale: i64) -> Value {
+        Value::Number(Number(d.0.with_scale_round(scale, RoundingMode::HalfUp)))
+    }
+
+    /// A string matching `s` literally in a LIKE pattern: `\`, `%` and `_`
+    /// are escaped with a backslash, assuming `ESCAPE '\'`.
+    pub fn like_literal(s: &str) -> Value {
+        let mut escaped = String::with_capacity(s.len());
+        for c in s.chars() {
+            if matches!(c, '\\' | '%' | '_') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        Value::String(escaped)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(bv) => Some(*bv),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Value::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(elems) => Some(elems),
+            _ => None,
+        }
+    }
+
+    /// True for both `Null` and `TypedNull`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null | Value::TypedNull(_))
+    }
+
+    /// The SQL literal this value inlines as, e.g. `'hello'`, `42`,
+    /// `ARRAY[1, 2]` or `NULL`. Strings follow standard SQL, where `\` is
+    /// not an escape character.
+    pub fn to_sql_literal(&self) -> Result<String, Error> {
+        Ok(Expr::try_from(self.clone())?.to_string())
+    }
+
+    /// A cheap, approximate length of the SQL this value renders as,
+    /// for pre-sizing buffers or checking limits without rendering.
+    pub fn estimated_sql_len(&self) -> usize {
+        // Separators between collection items: ", ".
+        let delimiters = |n: usize| n.saturating_sub(1) * 2;
+        match self {
+            Value::Bool(true) => 4,
+            Value::Bool(false) => 5,
+            Value::Number(Number(n)) => {
+                let (_, scale) = n.as_bigint_and_exponent();
+                let digits = n.digits() as usize;
+                let sign = usize::from(n.sign() == Sign::Minus);
+                let point = usize::from(scale > 0);
+                sign + digits.max(scale.max(0) as usize + 1) + point
+            }
+            Value::String(s) => s.len() + 2 + s.matches('\'').count(),
+            Value::TypedString(typ, s) => typ.len() + 1 + s.len() + 2 + s.matches('\'').count(),
+            Value::Array(elems) => {
+                7 + elems.iter().map(Value::estimated_sql_len).sum::<usize>()
+                    + delimiters(elems.len())
+            }
+            Value::Dict(pairs) => {
+                let entries: usize = pairs
+                    .iter()
+                    .map(|(k, v)| k.estimated_sql_len() + 2 + v.estimated_sql_len())
+                    .sum();
+                6 + entries + delimiters(pairs.len())
+            }
+            Value::Null => 4,
+            Value::TypedNull(typ) => 14 + typ.len(),
+            Value::Bytes(bytes) => 3 + bytes.len() * 2,
+        }
+    }
+
+    /// Collapses one level of nested arrays into the parent, keeping
+    /// non-array elements as they are. Non-arrays are returned unchanged.
+    pub fn flatten_arrays(self) -> Value {
+        let Value::Array(elems) = self else {
+            return self;
+        };
+        let mut flat = Vec::with_capacity(elems.len());
+        for elem in elems {
+            match elem {
+                Value::Array(inner) => flat.extend(inner),
+                _ => flat.push(elem),
+            }
+        }
+        Value::Array(flat)
+    }
+
+    /// Loose comparison: a numeric string equals the same number, and
+    /// decimals compare regardless of trailing zeros. Collections compare
+    /// element-wise with the same rules.
+    pub fn loose_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Number(n), Value::String(s)) | (Value::String(s), Value::Number(n)) => {
+                match s.trim().parse::<Number>() {
+                    Ok(m) => *n == m,
+                    Err(_) => false,
+                }
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| x.loose_eq(y))
+            }
+            (Value::Dict(a), Value::Dict(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|((ka, va), (kb, vb))| ka.loose_eq(kb) && va.loose_eq(vb))
+            }
+            _ => self == other,
+        }
+    }
+}
+
+fn typed_string_data_type(typ: &str) -> DataType {
+    match typ {
+        "datetime" | "date" => DataType::Date,
+        "interval" => DataType::Interval,
+        "timestamp" => DataType::Timestamp(None, TimezoneInfo::None),
+        "timestamptz" => DataType::Timestamp(None, TimezoneInfo::WithTimeZone),
+        "uuid" => DataType::Uuid,
+        "bytea" => DataType::Bytea,
+        _ => DataType::Unspecified,
+    }
+}
+
+/// Parses a type name such as `int` or `varchar(10)`, with nothing after
+/// it, so that it can't smuggle SQL into a cast.
+pub(crate) fn parse_data_type(typ: &str) -> Result<DataType, Error> {
+    let invalid = || Error::Conversion(format!("invalid type {:?}", typ));
+    let dialect = GenericDialect {};
+    let mut parser = Parser::new(&dialect)
+        .try_with_sql(typ)
+        .map_err(|_| invalid())?;
+    let data_type = parser.parse_data_type().map_err(|_| invalid())?;
+    if parser.peek_token().token != Token::EOF {
+        return Err(invalid());
+    }
+    Ok(data_type)
+}
+
+/// Doubles every `'`. sqlparser's escaper leaves `''` and `\'` as they are,
+/// so the string has to be escaped before it goes into the AST; with every
+/// quote already paired, the escaper writes it out unchanged.
+fn escape_quotes(s: String) -> String {
+    if s.contains('\'') {
+        s.replace('\'', "''")
+    } else {
+        s
+    }
+}
+
+fn write_quoted(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "'{}'", s.replace('\'', "''"))
+}
+
+/// Writes the SQL literal the value inlines as, without building an `Expr`.
+/// Every `'` in a string is doubled, and booleans are spelled `TRUE` and
+/// `FALSE`.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(true) => write!(f, "TRUE"),
+            Value::Bool(false) => write!(f, "FALSE"),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::String(s) => write_quoted(f, s),
+            Value::TypedString(typ, s) => match typed_string_data_type(typ) {
+                DataType::Unspecified => write_quoted(f, s),
+                data_type => {
+                    write!(f, "{} ", data_type)?;
+                    write_quoted(f, s)
+                }
+            },
+            Value::Array(elems) => {
+                write!(f, "ARRAY[")?;
+                for (i, elem) in elems.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", elem)?;
+                }
+                write!(f, "]")
+            }
+            Value::Dict(pairs) => {
+                write!(f, "MAP {{")?;
+                for (i, (k, v)) in pairs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", k, v)?;
+                }
+                write!(f, "}}")
+            }
+            Value::Null => write!(f, "NULL"),
+            // An invalid type can't be written as one; quoting keeps it inert.
+            Value::TypedNull(typ) => match parse_data_type(typ) {
+                Ok(data_type) => write!(f, "CAST(NULL AS {})", data_type),
+                Err(_) => write!(f, "CAST(NULL AS {})", Ident::with_quote('"', typ)),
+            },
+            Value::Bytes(bytes) => {
+                write!(f, "X'")?;
+                for b in bytes.iter() {
+                    write!(f, "{:02X}", b)?;
+                }
+                write!(f, "'")
+            }
+        }
+    }
+}
+
+/// Fails for a `TypedNull` whose type doesn't parse.
+impl TryFrom<Value> for Expr {
+    type Error = Error;
+
+    fn try_from(v: Value) -> Result<Self, Error> {
+        Ok(match v {
             Value::Bool(bv) => Expr::Value(AstValue::Boolean(bv)),
-            Value::Number(n) => Expr::Value(AstValue::Number(n, false)),
-            Value::String(s) => Expr::Value(AstValue::SingleQuotedString(s)),
+            Value::Number(n) => Expr::Value(AstValue::Number(n.0, false)),
+            Value::String(s) => Expr::Value(AstValue::SingleQuotedString(escape_quotes(s))),
             Value::TypedString(typ, s) => Expr::TypedString {
-                data_type: match typ.as_str() {
-                    "datetime" | "date" => DataType::Date,
-                    _ => DataType::Unspecified,
-                },
-                value: s,
+                data_type: typed_string_data_type(&typ),
+                value: escape_quotes(s),
             },
-            Value::Array(mut array) => {
-                let exprs = array.drain(..).map(|elem| elem.into());
+            Value::Array(array) => {
+                let exprs = array.into_iter().map(Expr::try_from);
                 Expr::Array(Array {
-                    elem: exprs.collect(),
-                    named: false,
+                    elem: exprs.collect::<Result<_, _>>()?,
+                    named: true,
                 })
             }
-            Value::Dict(mut pairs) => {
+            Value::Dict(pairs) => {
                 let entries = pairs
-                    .drain(..)
-                    .map(|(k, v)| MapEntry {
-                        key: Box::new(k.into()),
-                        value: Box::new(v.into()),
+                    .into_iter()
+                    .map(|(k, v)| {
+                        Ok(MapEntry {
+                            key: Box::new(k.try_into()?),
+                            value: Box::new(v.try_into()?),
+                        })
                     })
-                    .collect();
+                    .collect::<Result<_, Error>>()?;
                 Expr::Map(Map { entries })
             }
             Value::Null => Expr::Value(AstValue::Null),
-        }
+            Value::TypedNull(typ) => Expr::Cast {
+                kind: CastKind::Cast,
+                expr: Box::new(Expr::Value(AstValue::Null)),
+                data_type: parse_data_type(&typ)?,
+                format: None,
+            },
+            Value::Bytes(bytes) => {
+                let digits: String = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+                Expr::Value(AstValue::HexStringLiteral(digits))
+            }
+        })
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t: ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(i: $t) -> Self {
+                    Value::Number(Number::from(i))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, isize, usize);
+
+impl From<f32> for Value {
+    fn from(i: f32) -> Self {
+        Value::Number(Number::try_from(i).unwrap_or_default())
+    }
+}
+
+impl From<f64> for Value {
+    fn from(i: f64) -> Self {
+        Value::Number(Number::try_from(i).unwrap_or_default())
+    }
+}
+
+impl From<Number> for Value {
+    fn from(n: Number) -> Self {
+        Value::Number(n)
+    }
+}
+
+#[cfg(feature = "half")]
+impl From<half::f16> for Value {
+    fn from(i: half::f16) -> Self {
+        Value::from(f32::from(i))
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for Value {
+    fn from(d: rust_decimal::Decimal) -> Self {
+        // Goes through the string form so the scale is kept exactly.
+        Value::Number(d.to_string().parse::<Number>().unwrap_or_default())
     }
 }
 
-macro_rules! impl_from_int {
-    ($t: ty) => {
-        impl From<$t> for Value {
-            fn from(i: $t) -> Self {
-                Value::Number(BigDecimal::from(i))
+macro_rules! impl_try_into_int {
+    ($t: ty, $to: ident) => {
+        impl TryFrom<Value> for $t {
+            type Error = Error;
+
+            fn try_from(v: Value) -> Result<Self, Error> {
+                match v {
+                    Value::Number(n) if n.is_integer() => n.try_into(),
+                    _ => Err(Error::TypeMismatch(format!(
+                        "expected {}, got {:?}",
+                        stringify!($t),
+                        v
+                    ))),
+                }
             }
         }
     };
 }
 
-impl_from_int!(i8);
-impl_from_int!(i16);
-impl_from_int!(i32);
-impl_from_int!(i64);
-impl_from_int!(i128);
-impl_from_int!(u8);
-impl_from_int!(u16);
-impl_from_int!(u32);
-impl_from_int!(u64);
-impl_from_int!(u128);
+impl_try_into_int!(i32, to_i32);
+impl_try_into_int!(i64, to_i64);
+impl_try_into_int!(u64, to_u64);
+
+impl TryFrom<Value> for f64 {
+    type Error = Error;
 
-impl From<isize> for Value {
-    fn from(i: isize) -> Self {
-        Value::Number(BigDecimal::from(i64::try_from(i).unwrap_or(0i64)))
+    fn try_from(v: Value) -> Result<Self, Error> {
+        match v {
+            Value::Number(n) => n.try_into(),
+            _ => Err(Error::TypeMismatch(format!("expected f64, got {:?}", v))),
+        }
     }
 }
 
-impl From<usize> for Value {
-    fn from(i: usize) -> Self {
-        Value::Number(BigDecimal::from(u64::try_from(i).unwrap_or(0u64)))
+impl TryFrom<Value> for bool {
+    type Error = Error;
+
+    fn try_from(v: Value) -> Result<Self, Error> {
+        match v {
+            Value::Bool(bv) => Ok(bv),
+            _ => Err(Error::TypeMismatch(format!("expected bool, got {:?}", v))),
+        }
     }
 }
 
-impl From<f32> for Value {
-    fn from(i: f32) -> Self {
-        Value::Number(BigDecimal::try_from(i).unwrap_or(BigDecimal::from(0)))
+impl TryFrom<Value> for String {
+    type Error = Error;
+
+    fn try_from(v: Value) -> Result<Self, Error> {
+        match v {
+            Value::String(s) => Ok(s),
+            _ => Err(Error::TypeMismatch(format!("expected String, got {:?}", v))),
+        }
     }
 }
 
-impl From<f64> for Value {
-    fn from(i: f64) -> Self {
-        Value::Number(BigDecimal::try_from(i).unwrap_or(BigDecimal::from(0)))
+/// Accepts a `uuid` typed string or a plain string in any format
+/// `Uuid::parse_str` understands.
+#[cfg(feature = "uuid")]
+impl TryFrom<Value> for uuid::Uuid {
+    type Error = Error;
+
+    fn try_from(v: Value) -> Result<Self, Error> {
+        match v {
+            Value::String(s) => uuid::Uuid::parse_str(&s)
+                .map_err(|e| Error::Conversion(format!("invalid uuid {:?}: {}", s, e))),
+            Value::TypedString(typ, s) if typ == "uuid" => uuid::Uuid::parse_str(&s)
+                .map_err(|e| Error::Conversion(format!("invalid uuid {:?}: {}", s, e))),
+            _ => Err(Error::TypeMismatch(format!("expected uuid, got {:?}", v))),
+        }
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(opt: Option<T>) -> Self {
+        match opt {
+            Some(v) => v.into(),
+            None => Value::Null,
+        }
+    }
+}
+
+// Not a blanket impl over `T: Into<Value>`: an owned `Vec<u8>` decodes as
+// JSON (see wire.rs), and raw bytes convert from `&[u8]`.
+macro_rules! impl_from_vec {
+    ($($t: ty),*) => {
+        $(
+            impl From<Vec<$t>> for Value {
+                fn from(elems: Vec<$t>) -> Self {
+                    Value::Array(elems.into_iter().map(Into::into).collect())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_vec!(i8, i16, i32, i64, i128, u16, u32, u64, u128, isize, usize);
+impl_from_vec!(f32, f64, bool, &str, String, Value);
+
+impl From<&[u8]> for Value {
+    fn from(bytes: &[u8]) -> Self {
+        Value::Bytes(bytes.to_vec())
+    }
+}
+
+impl<K: Into<Value> + Ord, V: Into<Value>> From<HashMap<K, V>> for Value {
+    /// Entries are sorted by key so the rendered SQL is deterministic.
+    fn from(map: HashMap<K, V>) -> Self {
+        let mut entries: Vec<(K, V)> = map.into_iter().collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Value::Dict(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
     }
 }
 
@@ -110,3 +571,76 @@ impl From<bool> for Value {
         Value::Bool(bv)
     }
 }
+
+impl From<Duration> for Value {
+    /// Renders as `INTERVAL '<seconds> seconds'`, keeping sub-second
+    /// precision down to nanoseconds without trailing zeros.
+    fn from(d: Duration) -> Self {
+        let mut secs = d.as_secs().to_string();
+        let nanos = d.subsec_nanos();
+        if nanos > 0 {
+            let frac = format!("{:09}", nanos);
+            secs.push('.');
+            secs.push_str(frac.trim_end_matches('0'));
+        }
+        Value::TypedString("interval".to_string(), format!("{} seconds", secs))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for Value {
+    fn from(d: chrono::NaiveDate) -> Self {
+        Value::TypedString("date".to_string(), d.format("%Y-%m-%d").to_string())
+    }
+}
+
+/// Sub-second digits are kept (3, 6 or 9 of them) only when non-zero.
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for Value {
+    fn from(d: chrono::NaiveDateTime) -> Self {
+        let s = d.format("%Y-%m-%d %H:%M:%S%.f").to_string();
+        Value::TypedString("timestamp".to_string(), s)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Value {
+    fn from(d: chrono::DateTime<chrono::Utc>) -> Self {
+        let s = d.format("%Y-%m-%d %H:%M:%S%.f+00:00").to_string();
+        Value::TypedString("timestamptz".to_string(), s)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for Value {
+    fn from(u: uuid::Uuid) -> Self {
+        Value::TypedString("uuid".to_string(), u.hyphenated().to_string())
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    fn from(v: serde_json::Value) -> Self {
+        match v {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(bv) => Value::Bool(bv),
+            serde_json::Value::Number(n) => {
+                Value::Number(n.to_string().parse::<Number>().unwrap_or_default())
+            }
+            serde_json::Value::String(s) => Value::String(s),
+            serde_json::Value::Array(array) => {
+                Value::Array(array.into_iter().map(Value::from).collect())
+            }
+            serde_json::Value::Object(map) => Value::from(&map),
+        }
+    }
+}
+
+impl From<&serde_json::Map<String, serde_json::Value>> for Value {
+    fn from(map: &serde_json::Map<String, serde_json::Value>) -> Self {
+        let pairs = map
+            .iter()
+            .map(|(k, v)| (Value::String(k.clone()), Value::from(v.clone())))
+            .collect();
+        Value::Dict(pairs)
+    }
+}
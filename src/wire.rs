@@ -3,6 +3,9 @@ use {
     serde_json::{from_slice, to_vec},
 };
 
+#[cfg(feature = "binary")]
+use super::ParameterSet;
+
 impl TryFrom<&Vec<u8>> for Value {
     type Error = Error;
     fn try_from(bytes: &Vec<u8>) -> Result<Self, Self::Error> {
@@ -23,3 +26,17 @@ impl From<&Value> for Vec<u8> {
         to_vec(v).unwrap()
     }
 }
+
+#[cfg(feature = "binary")]
+impl ParameterSet {
+    /// Compact binary encoding of the values (postcard).
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        postcard::to_allocvec(&self.values).map_err(|e| Error::Binary(format!("{}", &e)))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let values: Vec<Value> =
+            postcard::from_bytes(bytes).map_err(|e| Error::Binary(format!("{}", &e)))?;
+        Ok(ParameterSet { values })
+    }
+}